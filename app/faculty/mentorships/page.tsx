@@ -0,0 +1,226 @@
+'use client'
+
+import { useCallback, useEffect, useState } from 'react'
+import Header from '@/components/Header'
+import Footer from '@/components/Footer'
+import { ArrowRight, Check, Loader2, Lock, Mail, X } from 'lucide-react'
+import { backendService } from '@/lib/services/backend'
+import { Mentorship } from '@/lib/types'
+import { cn } from '@/lib/utils'
+
+const TOKEN_KEY = 'faculty_token'
+
+// Faculty land here from mentorship request emails
+export default function FacultyMentorshipsPage() {
+    const [token, setToken] = useState<string | null>(null)
+    const [isReady, setIsReady] = useState(false)
+    const [email, setEmail] = useState('')
+    const [password, setPassword] = useState('')
+    const [mentorships, setMentorships] = useState<Mentorship[]>([])
+    const [notes, setNotes] = useState<Record<string, string>>({})
+    const [loading, setLoading] = useState(false)
+    const [error, setError] = useState('')
+
+    useEffect(() => {
+        setToken(localStorage.getItem(TOKEN_KEY))
+        setIsReady(true)
+    }, [])
+
+    const signOut = () => {
+        localStorage.removeItem(TOKEN_KEY)
+        setToken(null)
+        setMentorships([])
+    }
+
+    const load = useCallback(async (token: string) => {
+        setLoading(true)
+        setError('')
+        try {
+            setMentorships(await backendService.getMentorships(token))
+        } catch {
+            // Expired or revoked sessions have to sign in again
+            localStorage.removeItem(TOKEN_KEY)
+            setToken(null)
+        } finally {
+            setLoading(false)
+        }
+    }, [])
+
+    useEffect(() => {
+        if (token) load(token)
+    }, [token, load])
+
+    const handleLogin = async (e: React.FormEvent) => {
+        e.preventDefault()
+        setLoading(true)
+        setError('')
+        try {
+            const response = await backendService.facultyLogin(email, password)
+            localStorage.setItem(TOKEN_KEY, response.token)
+            setToken(response.token)
+        } catch {
+            setError('Invalid credentials. Please try again.')
+        } finally {
+            setLoading(false)
+        }
+    }
+
+    const respond = async (mentorId: string, status: 'approved' | 'declined') => {
+        if (!token) return
+        setError('')
+        try {
+            await backendService.respondToMentorship(token, mentorId, status, notes[mentorId])
+            await load(token)
+        } catch (err: any) {
+            setError(err.message || 'Failed to record your response')
+        }
+    }
+
+    if (!isReady) return null
+
+    return (
+        <div className="flex flex-col min-h-screen">
+            <Header />
+            <main className="flex-1 pt-20 bg-white dark:bg-ash-950">
+                <div className="max-w-4xl mx-auto px-4 py-12">
+                    <div className="flex items-center justify-between mb-10">
+                        <h1 className="text-4xl font-display font-black tracking-tighter text-ash-900 dark:text-white uppercase">
+                            Mentorship <span className="text-gradient">Requests</span>
+                        </h1>
+                        {token && (
+                            <button
+                                onClick={signOut}
+                                className="text-sm font-bold text-ash-500 hover:text-flame-600 transition-colors"
+                            >
+                                Sign Out
+                            </button>
+                        )}
+                    </div>
+
+                    {error && (
+                        <div className="mb-6 p-4 rounded-2xl bg-ember-500/10 border border-ember-500/20 text-ember-500 text-sm font-medium">
+                            {error}
+                        </div>
+                    )}
+
+                    {!token ? (
+                        <form onSubmit={handleLogin} className="max-w-md space-y-6">
+                            <p className="text-ash-500 dark:text-ash-400">
+                                Sign in with your faculty account to approve or decline teams that asked you to mentor them.
+                            </p>
+                            <div className="relative">
+                                <Mail className="absolute left-4 top-1/2 -translate-y-1/2 h-5 w-5 text-ash-400" />
+                                <input
+                                    type="email"
+                                    value={email}
+                                    onChange={(e) => setEmail(e.target.value)}
+                                    placeholder="yourname@srec.ac.in"
+                                    className="w-full pl-12 pr-4 py-4 bg-ash-50 dark:bg-ash-900 border border-ash-200 dark:border-ash-700 rounded-2xl text-ash-900 dark:text-white placeholder:text-ash-400 focus:outline-none focus:ring-2 focus:ring-flame-500/50 transition-all"
+                                    required
+                                />
+                            </div>
+                            <div className="relative">
+                                <Lock className="absolute left-4 top-1/2 -translate-y-1/2 h-5 w-5 text-ash-400" />
+                                <input
+                                    type="password"
+                                    value={password}
+                                    onChange={(e) => setPassword(e.target.value)}
+                                    placeholder="••••••••"
+                                    className="w-full pl-12 pr-4 py-4 bg-ash-50 dark:bg-ash-900 border border-ash-200 dark:border-ash-700 rounded-2xl text-ash-900 dark:text-white placeholder:text-ash-400 focus:outline-none focus:ring-2 focus:ring-flame-500/50 transition-all"
+                                    required
+                                />
+                            </div>
+                            <button
+                                type="submit"
+                                disabled={loading}
+                                className="w-full group flex items-center justify-center px-8 py-5 bg-ash-900 dark:bg-white text-white dark:text-black hover:bg-flame-600 dark:hover:bg-flame-500 hover:text-white transition-all font-bold rounded-2xl disabled:opacity-70 disabled:cursor-not-allowed"
+                            >
+                                {loading ? (
+                                    <Loader2 className="h-5 w-5 animate-spin" />
+                                ) : (
+                                    <>
+                                        Sign In
+                                        <ArrowRight className="ml-2 h-5 w-5 transition-transform group-hover:translate-x-1" />
+                                    </>
+                                )}
+                            </button>
+                        </form>
+                    ) : loading && mentorships.length === 0 ? (
+                        <Loader2 className="h-8 w-8 animate-spin text-ash-400 mx-auto" />
+                    ) : mentorships.length === 0 ? (
+                        <p className="text-ash-500 dark:text-ash-400">No team has asked you to mentor them yet.</p>
+                    ) : (
+                        <div className="space-y-6">
+                            {mentorships.map((m) => (
+                                <div
+                                    key={m.mentorId}
+                                    className="p-6 border border-ash-100 dark:border-ash-800 rounded-3xl space-y-4"
+                                >
+                                    <div className="flex items-start justify-between gap-4">
+                                        <div>
+                                            <p className="font-bold text-lg text-ash-900 dark:text-white">{m.teamName}</p>
+                                            <p className="text-sm text-ash-500">
+                                                {m.hackathonName} · {m.semester}
+                                            </p>
+                                        </div>
+                                        <span
+                                            className={cn(
+                                                'px-3 py-1 rounded-full text-xs font-bold uppercase tracking-widest',
+                                                m.status === 'approved' && 'bg-green-500/10 text-green-600',
+                                                m.status === 'declined' && 'bg-ember-500/10 text-ember-500',
+                                                m.status === 'pending' && 'bg-flame-500/10 text-flame-600'
+                                            )}
+                                        >
+                                            {m.status}
+                                        </span>
+                                    </div>
+
+                                    <ul className="text-sm text-ash-600 dark:text-ash-300 space-y-1">
+                                        {m.participants.map((p, idx) => (
+                                            <li key={idx}>
+                                                {p.name} · {p.department} · {p.academicYear}
+                                            </li>
+                                        ))}
+                                    </ul>
+
+                                    {m.status === 'pending' ? (
+                                        <div className="space-y-3">
+                                            <input
+                                                type="text"
+                                                value={notes[m.mentorId] || ''}
+                                                onChange={(e) => setNotes({ ...notes, [m.mentorId]: e.target.value })}
+                                                placeholder="Optional note to the team"
+                                                className="w-full px-4 py-2 border rounded-xl outline-none dark:bg-ash-900 dark:border-ash-700"
+                                            />
+                                            <div className="flex gap-3">
+                                                <button
+                                                    onClick={() => respond(m.mentorId, 'approved')}
+                                                    className="flex items-center px-5 py-2 bg-ash-900 dark:bg-white text-white dark:text-black hover:bg-flame-600 hover:text-white font-bold rounded-xl transition-all"
+                                                >
+                                                    <Check className="mr-2 h-4 w-4" />
+                                                    Approve
+                                                </button>
+                                                <button
+                                                    onClick={() => respond(m.mentorId, 'declined')}
+                                                    className="flex items-center px-5 py-2 border border-ash-200 dark:border-ash-700 text-ash-700 dark:text-ash-300 hover:border-ember-500 hover:text-ember-500 font-bold rounded-xl transition-all"
+                                                >
+                                                    <X className="mr-2 h-4 w-4" />
+                                                    Decline
+                                                </button>
+                                            </div>
+                                        </div>
+                                    ) : (
+                                        m.responseNote && (
+                                            <p className="text-sm text-ash-500">Your note: {m.responseNote}</p>
+                                        )
+                                    )}
+                                </div>
+                            ))}
+                        </div>
+                    )}
+                </div>
+            </main>
+            <Footer />
+        </div>
+    )
+}
@@ -8,10 +8,11 @@ import { useState, useEffect, Suspense } from 'react'
 import { useHackathonStore } from '@/lib/store/hackathonStore'
 import { useSubmissionStore } from '@/lib/store/submissionStore'
 import { useRouter, useSearchParams } from 'next/navigation'
-import { Participant, Mentor, AcademicYear, Department } from '@/lib/types'
+import { Participant, Mentor, AcademicYear, Department, FacultyMember } from '@/lib/types'
 import { cn } from '@/lib/utils'
 import { useStudentStore } from '@/lib/store/studentStore'
 import { DEPARTMENTS } from '@/lib/constants/departments'
+import { backendService } from '@/lib/services/backend'
 
 const ACADEMIC_YEARS: AcademicYear[] = [
   'First Year',
@@ -39,6 +40,7 @@ function SubmitForm() {
   const [mentors, setMentors] = useState<Mentor[]>([])
   const [hasMentor, setHasMentor] = useState(false)
   const [mentorCount, setMentorCount] = useState(0)
+  const [faculty, setFaculty] = useState<FacultyMember[]>([])
   const [confirmed, setConfirmed] = useState(false)
   const [errors, setErrors] = useState<Record<string, string>>({})
 
@@ -58,6 +60,10 @@ function SubmitForm() {
     fetchHackathons()
   }, [fetchHackathons])
 
+  useEffect(() => {
+    backendService.getFaculty().then(setFaculty).catch(() => setFaculty([]))
+  }, [])
+
   // Initialize participants array
   useEffect(() => {
     if (participants.length !== participantCount) {
@@ -83,6 +89,7 @@ function SubmitForm() {
         newMentors.push({
           name: mentors[i]?.name || '',
           department: mentors[i]?.department || 'Computer Science',
+          facultyId: mentors[i]?.facultyId,
         })
       }
       setMentors(newMentors)
@@ -122,8 +129,13 @@ function SubmitForm() {
 
     if (currentStep === 'mentors' && hasMentor) {
       mentors.forEach((m, idx) => {
-        if (!m.name.trim()) newErrors[`mentorName_${idx}`] = 'Mentor name is required'
+        if (!m.facultyId && !m.name.trim()) newErrors[`mentorName_${idx}`] = 'Mentor name is required'
       })
+
+      const facultyIds = mentors.filter((m) => m.facultyId).map((m) => m.facultyId)
+      if (new Set(facultyIds).size !== facultyIds.length) {
+        newErrors.duplicateMentor = 'The same faculty member is listed twice'
+      }
     }
 
     if (currentStep === 'review' && !confirmed) {
@@ -393,37 +405,69 @@ function SubmitForm() {
                   {mentors.map((m, idx) => (
                     <div key={idx} className="p-6 border border-ash-100 dark:border-ash-800 rounded-3xl space-y-4">
                       <p className="text-xs font-bold text-ash-300 uppercase tracking-widest">Mentor Node {idx + 1}</p>
-                      <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
+                      {faculty.length > 0 && (
                         <div className="space-y-2">
-                          <label className="text-xs font-bold text-ash-500">Full Name</label>
-                          <input
-                            type="text"
-                            value={m.name}
-                            onChange={(e) => {
-                              const updated = [...mentors]
-                              updated[idx].name = e.target.value
-                              setMentors(updated)
-                            }}
-                            className="w-full px-4 py-2 border rounded-xl outline-none dark:bg-ash-900 dark:border-ash-700"
-                          />
-                        </div>
-                        <div className="space-y-2">
-                          <label className="text-xs font-bold text-ash-500">Department</label>
+                          <label className="text-xs font-bold text-ash-500">Faculty Member</label>
                           <select
-                            value={m.department}
+                            value={m.facultyId || ''}
                             onChange={(e) => {
                               const updated = [...mentors]
-                              updated[idx].department = e.target.value as any
+                              const picked = faculty.find((f) => f.id === e.target.value)
+                              updated[idx] = picked
+                                ? { name: picked.name, department: picked.department, facultyId: picked.id }
+                                : { name: '', department: 'Computer Science' }
                               setMentors(updated)
                             }}
                             className="w-full px-4 py-2 border rounded-xl outline-none dark:bg-ash-900 dark:border-ash-700"
                           >
-                            {DEPARTMENTS.map(d => <option key={d} value={d}>{d}</option>)}
+                            <option value="">Not listed - enter details below</option>
+                            {faculty.map((f) => (
+                              <option key={f.id} value={f.id}>
+                                {f.name} ({f.department})
+                              </option>
+                            ))}
                           </select>
+                          {m.facultyId && (
+                            <p className="text-xs text-ash-500">
+                              {m.name} will be asked to approve the mentorship request.
+                            </p>
+                          )}
                         </div>
-                      </div>
+                      )}
+                      {!m.facultyId && (
+                        <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
+                          <div className="space-y-2">
+                            <label className="text-xs font-bold text-ash-500">Full Name</label>
+                            <input
+                              type="text"
+                              value={m.name}
+                              onChange={(e) => {
+                                const updated = [...mentors]
+                                updated[idx].name = e.target.value
+                                setMentors(updated)
+                              }}
+                              className="w-full px-4 py-2 border rounded-xl outline-none dark:bg-ash-900 dark:border-ash-700"
+                            />
+                          </div>
+                          <div className="space-y-2">
+                            <label className="text-xs font-bold text-ash-500">Department</label>
+                            <select
+                              value={m.department}
+                              onChange={(e) => {
+                                const updated = [...mentors]
+                                updated[idx].department = e.target.value as any
+                                setMentors(updated)
+                              }}
+                              className="w-full px-4 py-2 border rounded-xl outline-none dark:bg-ash-900 dark:border-ash-700"
+                            >
+                              {DEPARTMENTS.map(d => <option key={d} value={d}>{d}</option>)}
+                            </select>
+                          </div>
+                        </div>
+                      )}
                     </div>
                   ))}
+                  {errors.duplicateMentor && <p className="text-ember-500 text-sm text-center">{errors.duplicateMentor}</p>}
                 </div>
               )}
             </div>
@@ -0,0 +1,15 @@
+-- Hackathon outcomes recorded per submission
+CREATE TABLE IF NOT EXISTS submission_results (
+    submission_id UUID PRIMARY KEY REFERENCES submissions(id) ON DELETE CASCADE,
+    outcome VARCHAR(50) NOT NULL CHECK (outcome IN ('winner', 'runner_up', 'finalist', 'special_mention')),
+    rank INTEGER CHECK (rank > 0),
+    award_title VARCHAR(255),
+    prize_amount BIGINT CHECK (prize_amount >= 0),
+    certificate_ref VARCHAR(255),
+    recorded_by UUID REFERENCES admins(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS idx_submission_results_outcome ON submission_results(outcome);
@@ -0,0 +1,3 @@
+-- Per-hackathon custom registration questions and the answers submitted for them
+ALTER TABLE hackathons ADD COLUMN IF NOT EXISTS registration_fields JSONB NOT NULL DEFAULT '[]'::jsonb;
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS answers JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,9 @@
+-- Per-hackathon team size limits and structured eligibility.
+-- Defaults match the limits previously hardcoded in submit_participation.
+ALTER TABLE hackathons ADD COLUMN IF NOT EXISTS min_team_size INTEGER NOT NULL DEFAULT 1 CHECK (min_team_size >= 1);
+ALTER TABLE hackathons ADD COLUMN IF NOT EXISTS max_team_size INTEGER NOT NULL DEFAULT 10;
+ALTER TABLE hackathons ADD COLUMN IF NOT EXISTS max_mentors INTEGER NOT NULL DEFAULT 5 CHECK (max_mentors >= 0);
+ALTER TABLE hackathons ADD COLUMN IF NOT EXISTS allowed_years INTEGER[] NOT NULL DEFAULT '{}';
+ALTER TABLE hackathons ADD COLUMN IF NOT EXISTS allowed_branches TEXT[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE hackathons ADD CONSTRAINT hackathons_team_size_range CHECK (max_team_size >= min_team_size);
@@ -0,0 +1,11 @@
+-- Optional team capacity per hackathon; teams beyond it are waitlisted
+ALTER TABLE hackathons ADD COLUMN IF NOT EXISTS team_capacity INTEGER CHECK (team_capacity > 0);
+
+-- New submission states for the waitlist and team withdrawals
+ALTER TABLE submissions DROP CONSTRAINT IF EXISTS submissions_status_check;
+ALTER TABLE submissions ADD CONSTRAINT submissions_status_check
+    CHECK (status IN ('submitted', 'verified', 'archived', 'waitlisted', 'withdrawn', 'rejected'));
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS waitlisted_at TIMESTAMP WITH TIME ZONE;
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS idx_submissions_waitlist ON submissions(hackathon_id, waitlisted_at) WHERE status = 'waitlisted';
@@ -0,0 +1,11 @@
+-- Per-category delivery preferences: instant email, weekly digest, or off
+ALTER TABLE students ADD COLUMN IF NOT EXISTS notify_submission VARCHAR(20) NOT NULL DEFAULT 'instant'
+    CHECK (notify_submission IN ('instant', 'digest', 'off'));
+ALTER TABLE students ADD COLUMN IF NOT EXISTS notify_hackathon VARCHAR(20) NOT NULL DEFAULT 'digest'
+    CHECK (notify_hackathon IN ('instant', 'digest', 'off'));
+ALTER TABLE students ADD COLUMN IF NOT EXISTS notify_blog VARCHAR(20) NOT NULL DEFAULT 'digest'
+    CHECK (notify_blog IN ('instant', 'digest', 'off'));
+
+-- Lets scheduled jobs be enqueued at most once per period
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS dedupe_key VARCHAR(255);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_jobs_dedupe_key ON jobs(dedupe_key) WHERE dedupe_key IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Students can hide themselves from the public leaderboard
+ALTER TABLE students ADD COLUMN IF NOT EXISTS leaderboard_opt_out BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,24 @@
+-- Faculty accounts; teams pick their mentors from here
+CREATE TABLE IF NOT EXISTS faculty (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    email VARCHAR(255) UNIQUE NOT NULL,
+    password_hash VARCHAR(255) NOT NULL,
+    department VARCHAR(255) NOT NULL,
+    designation VARCHAR(255),
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Mentors picked from the faculty list wait for the faculty member to respond.
+-- Free-text mentors from before this change have nobody to ask, so they count as approved.
+ALTER TABLE mentors ADD COLUMN IF NOT EXISTS faculty_id UUID REFERENCES faculty(id) ON DELETE SET NULL;
+ALTER TABLE mentors ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'approved'
+    CHECK (status IN ('pending', 'approved', 'declined'));
+ALTER TABLE mentors ADD COLUMN IF NOT EXISTS response_note TEXT;
+ALTER TABLE mentors ADD COLUMN IF NOT EXISTS responded_at TIMESTAMP WITH TIME ZONE;
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS idx_faculty_department ON faculty(department);
+CREATE INDEX IF NOT EXISTS idx_mentors_faculty ON mentors(faculty_id, status);
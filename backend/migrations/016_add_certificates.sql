@@ -0,0 +1,33 @@
+-- Certificate layout for a hackathon; the background image lives in storage
+CREATE TABLE IF NOT EXISTS certificate_templates (
+    hackathon_id UUID PRIMARY KEY REFERENCES hackathons(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    signatory_name VARCHAR(255),
+    signatory_title VARCHAR(255),
+    background_key VARCHAR(512),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- One certificate per participant. Names are copied at issue time so a
+-- verification shows exactly what was printed. Certificates follow the team's
+-- status and result after issue, so they can be revoked or reissued.
+CREATE TABLE IF NOT EXISTS certificates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    verification_code VARCHAR(20) UNIQUE NOT NULL,
+    participant_id UUID UNIQUE NOT NULL REFERENCES participants(id) ON DELETE CASCADE,
+    submission_id UUID NOT NULL REFERENCES submissions(id) ON DELETE CASCADE,
+    hackathon_id UUID NOT NULL REFERENCES hackathons(id) ON DELETE CASCADE,
+    participant_name VARCHAR(255) NOT NULL,
+    participant_email VARCHAR(255) NOT NULL,
+    team_name VARCHAR(255) NOT NULL,
+    hackathon_name VARCHAR(255) NOT NULL,
+    award VARCHAR(255) NOT NULL,
+    issued_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    reissued_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS idx_certificates_hackathon ON certificates(hackathon_id);
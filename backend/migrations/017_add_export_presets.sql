@@ -0,0 +1,12 @@
+-- Named column/layout selections admins reuse for submission exports
+CREATE TABLE IF NOT EXISTS export_presets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    admin_id UUID NOT NULL REFERENCES admins(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    columns TEXT[] NOT NULL,
+    layout VARCHAR(20) NOT NULL DEFAULT 'participant' CHECK (layout IN ('participant', 'team')),
+    format VARCHAR(10) NOT NULL DEFAULT 'csv' CHECK (format IN ('csv', 'xlsx')),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (admin_id, name)
+);
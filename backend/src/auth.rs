@@ -5,9 +5,11 @@ use argon2::{
 };
 use chrono::Utc;
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
-use crate::models::Claims;
+use crate::models::{Claims, StreamTicketClaims};
 
 const JWT_EXPIRATION: i64 = 24 * 60 * 60; // 24 hours
+const STREAM_TICKET_EXPIRATION: i64 = 60; // only needs to last until the stream connects
+const STREAM_TICKET_PURPOSE: &str = "admin_events";
 
 pub fn hash_password(password: &str) -> AppResult<String> {
     let salt = SaltString::generate(OsRng);
@@ -55,3 +57,83 @@ pub fn verify_jwt(token: &str, secret: &str) -> AppResult<Claims> {
     .map(|data| data.claims)
     .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))
 }
+
+pub fn create_stream_ticket(claims: &Claims, secret: &str) -> AppResult<String> {
+    let ticket = StreamTicketClaims {
+        sub: claims.sub.clone(),
+        purpose: STREAM_TICKET_PURPOSE.to_string(),
+        exp: (Utc::now().timestamp() + STREAM_TICKET_EXPIRATION).min(claims.exp),
+        session_exp: claims.exp,
+    };
+
+    encode(
+        &Header::default(),
+        &ticket,
+        &EncodingKey::from_secret(secret.as_ref()),
+    )
+    .map_err(|e| AppError::InternalError(format!("JWT creation failed: {}", e)))
+}
+
+pub fn verify_stream_ticket(ticket: &str, secret: &str) -> AppResult<StreamTicketClaims> {
+    let claims: StreamTicketClaims = decode(
+        ticket,
+        &DecodingKey::from_secret(secret.as_ref()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|e| AppError::Unauthorized(format!("Invalid ticket: {}", e)))?;
+
+    if claims.purpose != STREAM_TICKET_PURPOSE {
+        return Err(AppError::Unauthorized("Invalid ticket".to_string()));
+    }
+
+    Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test-secret";
+
+    fn admin_claims() -> Claims {
+        verify_jwt(
+            &create_jwt("admin-1", "admin@srec.ac.in", "admin", SECRET).unwrap(),
+            SECRET,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn stream_ticket_carries_the_session_expiry() {
+        let claims = admin_claims();
+        let ticket = create_stream_ticket(&claims, SECRET).unwrap();
+        let ticket_claims = verify_stream_ticket(&ticket, SECRET).unwrap();
+
+        assert_eq!(ticket_claims.sub, "admin-1");
+        assert_eq!(ticket_claims.session_exp, claims.exp);
+        assert!(ticket_claims.exp <= Utc::now().timestamp() + STREAM_TICKET_EXPIRATION);
+    }
+
+    #[test]
+    fn stream_ticket_never_outlives_the_session() {
+        let mut claims = admin_claims();
+        claims.exp = Utc::now().timestamp() + 10;
+        let ticket = create_stream_ticket(&claims, SECRET).unwrap();
+
+        assert_eq!(
+            verify_stream_ticket(&ticket, SECRET).unwrap().exp,
+            claims.exp
+        );
+    }
+
+    #[test]
+    fn tickets_and_session_tokens_are_not_interchangeable() {
+        let session = create_jwt("admin-1", "admin@srec.ac.in", "admin", SECRET).unwrap();
+        let ticket = create_stream_ticket(&admin_claims(), SECRET).unwrap();
+
+        assert!(verify_stream_ticket(&session, SECRET).is_err());
+        assert!(verify_jwt(&ticket, SECRET).is_err());
+        assert!(verify_stream_ticket(&ticket, "other-secret").is_err());
+    }
+}
@@ -0,0 +1,382 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    Certificate, CertificateTemplate, CertificateVerification, CERTIFICATE_PLACEHOLDERS,
+};
+use crate::pdf::{self, Background, CertificateContent};
+use crate::storage::Storage;
+use crate::utils::sanitize_file_name;
+use rand::Rng;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+// Only teams the admins verified get certificates
+const CERTIFIED_STATUSES: [&str; 1] = ["verified"];
+
+// Issued certificates stop verifying while the team is in one of these
+const REVOKED_STATUSES: [&str; 2] = ["rejected", "withdrawn"];
+
+// Award printed on a certificate, from the team's recorded result
+const AWARD_SQL: &str =
+    "COALESCE(r.award_title, INITCAP(REPLACE(r.outcome, '_', ' ')), 'Participation')";
+
+// Decoded backgrounds kept in memory; only a handful of hackathons hand out
+// certificates at the same time
+const MAX_CACHED_BACKGROUNDS: usize = 16;
+
+// Crockford base32: no I, L, O or U, so codes survive being read aloud or retyped
+const CODE_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+// Formatted as XXXX-XXXX-XXXX
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    let chars: Vec<char> = (0..12)
+        .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+        .collect();
+    chars
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// Codes are matched case-insensitively and with or without dashes
+pub fn normalize_code(code: &str) -> String {
+    let compact: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    compact
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// Issues certificates to every participant of the hackathon's verified teams
+// who doesn't have one yet. Returns how many were issued.
+pub async fn issue(conn: &mut PgConnection, hackathon_id: Uuid) -> AppResult<usize> {
+    #[derive(sqlx::FromRow)]
+    struct Pending {
+        participant_id: Uuid,
+        submission_id: Uuid,
+        name: String,
+        email: String,
+        team_name: String,
+        hackathon_name: String,
+        award: String,
+    }
+
+    let sql = format!(
+        "SELECT p.id AS participant_id, s.id AS submission_id, p.name, p.email, s.team_name,
+                h.name AS hackathon_name, {} AS award
+         FROM participants p
+         JOIN submissions s ON p.submission_id = s.id
+         JOIN hackathons h ON s.hackathon_id = h.id
+         LEFT JOIN submission_results r ON r.submission_id = s.id
+         LEFT JOIN certificates c ON c.participant_id = p.id
+         WHERE s.hackathon_id = $1 AND s.status = ANY($2) AND c.id IS NULL",
+        AWARD_SQL
+    );
+    let pending: Vec<Pending> = sqlx::query_as(&sql)
+        .bind(hackathon_id)
+        .bind(&CERTIFIED_STATUSES[..])
+        .fetch_all(&mut *conn)
+        .await?;
+
+    for p in &pending {
+        sqlx::query(
+            "INSERT INTO certificates (verification_code, participant_id, submission_id, hackathon_id,
+                                       participant_name, participant_email, team_name, hackathon_name, award)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(generate_code())
+        .bind(p.participant_id)
+        .bind(p.submission_id)
+        .bind(hackathon_id)
+        .bind(&p.name)
+        .bind(&p.email)
+        .bind(&p.team_name)
+        .bind(&p.hackathon_name)
+        .bind(&p.award)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(pending.len())
+}
+
+// Rewrites the award on a team's certificates after its result changes, so
+// downloads and the verify endpoint show the current award
+pub async fn reissue(conn: &mut PgConnection, submission_id: Uuid) -> AppResult<()> {
+    let sql = format!(
+        "UPDATE certificates c SET award = a.award, reissued_at = NOW()
+         FROM (
+             SELECT s.id, {} AS award
+             FROM submissions s
+             LEFT JOIN submission_results r ON r.submission_id = s.id
+             WHERE s.id = $1
+         ) a
+         WHERE c.submission_id = a.id AND c.award <> a.award",
+        AWARD_SQL
+    );
+    sqlx::query(&sql)
+        .bind(submission_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+// Revokes a team's certificates when it is rejected or withdraws, and
+// restores them if the team is reinstated
+pub async fn sync_revocation(
+    conn: &mut PgConnection,
+    submission_id: Uuid,
+    status: &str,
+) -> AppResult<()> {
+    sqlx::query(
+        "UPDATE certificates SET
+            revoked_at = CASE WHEN $2 THEN COALESCE(revoked_at, NOW()) END
+         WHERE submission_id = $1",
+    )
+    .bind(submission_id)
+    .bind(REVOKED_STATUSES.contains(&status))
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn find_by_code(db: &PgPool, code: &str) -> AppResult<Certificate> {
+    sqlx::query_as("SELECT * FROM certificates WHERE verification_code = $1")
+        .bind(normalize_code(code))
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Certificate not found".to_string()))
+}
+
+// Validity follows the team's current status rather than the stored flag
+pub async fn verify(db: &PgPool, code: &str) -> AppResult<CertificateVerification> {
+    let certificate = find_by_code(db, code).await?;
+    let status: String = sqlx::query_scalar("SELECT status FROM submissions WHERE id = $1")
+        .bind(certificate.submission_id)
+        .fetch_one(db)
+        .await?;
+
+    let valid = !REVOKED_STATUSES.contains(&status.as_str());
+    Ok(CertificateVerification::new(certificate, valid))
+}
+
+// Decoded backgrounds by storage key. Every upload is stored under a new key,
+// so a cached entry never goes stale.
+#[derive(Default)]
+pub struct BackgroundCache {
+    entries: Mutex<HashMap<String, Arc<Background>>>,
+}
+
+impl BackgroundCache {
+    fn get(&self, key: &str) -> Option<Arc<Background>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .cloned()
+    }
+
+    fn insert(&self, key: &str, background: Arc<Background>) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= MAX_CACHED_BACKGROUNDS {
+            entries.clear();
+        }
+        entries.insert(key.to_string(), background);
+    }
+
+    pub fn remove(&self, key: &str) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+    }
+}
+
+// Template and decoded background for a hackathon's certificates
+pub async fn load_template(
+    db: &PgPool,
+    storage: &dyn Storage,
+    cache: &BackgroundCache,
+    hackathon_id: Uuid,
+) -> AppResult<(CertificateTemplate, Option<Arc<Background>>)> {
+    let template: CertificateTemplate =
+        sqlx::query_as("SELECT * FROM certificate_templates WHERE hackathon_id = $1")
+            .bind(hackathon_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| {
+                AppError::BadRequest(
+                    "Upload a certificate template for this hackathon first".to_string(),
+                )
+            })?;
+
+    let background = match &template.background_key {
+        Some(key) => match cache.get(key) {
+            Some(background) => Some(background),
+            None => {
+                let data = storage.get(key).await?;
+                let background = tokio::task::spawn_blocking(move || pdf::decode_background(&data))
+                    .await
+                    .map_err(|e| {
+                        AppError::InternalError(format!("Background decoding failed: {}", e))
+                    })??;
+                let background = Arc::new(background);
+                cache.insert(key, background.clone());
+                Some(background)
+            }
+        },
+        None => None,
+    };
+
+    Ok((template, background))
+}
+
+fn fill(text: &str, certificate: &Certificate) -> String {
+    let date = certificate.issued_at.format("%d %B %Y").to_string();
+    let values = [
+        certificate.participant_name.as_str(),
+        certificate.team_name.as_str(),
+        certificate.hackathon_name.as_str(),
+        certificate.award.as_str(),
+        date.as_str(),
+    ];
+
+    CERTIFICATE_PLACEHOLDERS
+        .iter()
+        .zip(values)
+        .fold(text.to_string(), |text, (placeholder, value)| {
+            text.replace(placeholder, value)
+        })
+}
+
+pub fn render(
+    template: &CertificateTemplate,
+    background: Option<&Background>,
+    certificate: &Certificate,
+    public_api_url: &str,
+) -> AppResult<Vec<u8>> {
+    let title = fill(&template.title, certificate);
+    let body = fill(&template.body, certificate);
+    let verification = format!(
+        "Verification code {} - verify at {}/api/certificates/{}",
+        certificate.verification_code,
+        public_api_url.trim_end_matches('/'),
+        certificate.verification_code
+    );
+
+    pdf::generate_certificate(
+        &CertificateContent {
+            title: &title,
+            body: &body,
+            signatory_name: template.signatory_name.as_deref(),
+            signatory_title: template.signatory_title.as_deref(),
+            verification: &verification,
+        },
+        background,
+    )
+}
+
+pub fn file_name(certificate: &Certificate) -> String {
+    // Slashes in team names would otherwise be read as directories
+    let name = format!(
+        "{}-{}-{}.pdf",
+        certificate.team_name, certificate.participant_name, certificate.verification_code
+    );
+    sanitize_file_name(&name.replace(['/', '\\'], "_"))
+}
+
+// All of a hackathon's certificates in one archive, one PDF per participant
+pub fn zip_certificates(
+    template: &CertificateTemplate,
+    background: Option<&Background>,
+    certificates: &[Certificate],
+    public_api_url: &str,
+) -> AppResult<Vec<u8>> {
+    let zip_error = |e: zip::result::ZipError| AppError::InternalError(format!("ZIP error: {}", e));
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for certificate in certificates {
+        let data = render(template, background, certificate, public_api_url)?;
+        zip.start_file(file_name(certificate), SimpleFileOptions::default())
+            .map_err(zip_error)?;
+        zip.write_all(&data)
+            .map_err(|e| AppError::InternalError(format!("ZIP error: {}", e)))?;
+    }
+
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn certificate() -> Certificate {
+        Certificate {
+            id: Uuid::new_v4(),
+            verification_code: "ABCD-EFGH-JKMN".to_string(),
+            participant_id: Uuid::new_v4(),
+            submission_id: Uuid::new_v4(),
+            hackathon_id: Uuid::new_v4(),
+            participant_name: "Asha Rao".to_string(),
+            participant_email: "asha@srec.ac.in".to_string(),
+            team_name: "Null Pointers".to_string(),
+            hackathon_name: "Smart India Hackathon".to_string(),
+            award: "Winner".to_string(),
+            issued_at: Utc.with_ymd_and_hms(2026, 3, 7, 10, 0, 0).unwrap(),
+            revoked_at: None,
+            reissued_at: None,
+        }
+    }
+
+    #[test]
+    fn normalize_code_ignores_case_and_separators() {
+        assert_eq!(normalize_code("abcd-efgh-jkmn"), "ABCD-EFGH-JKMN");
+        assert_eq!(normalize_code("ABCDEFGHJKMN"), "ABCD-EFGH-JKMN");
+        assert_eq!(normalize_code(" abcd efgh_jkmn "), "ABCD-EFGH-JKMN");
+    }
+
+    #[test]
+    fn normalize_code_keeps_partial_groups() {
+        assert_eq!(normalize_code("abcde"), "ABCD-E");
+        assert_eq!(normalize_code("--"), "");
+    }
+
+    #[test]
+    fn generated_codes_are_already_normalized() {
+        let code = generate_code();
+        assert_eq!(code.len(), 14);
+        assert_eq!(normalize_code(&code), code);
+    }
+
+    #[test]
+    fn fill_replaces_every_placeholder() {
+        let text = "{name} of {team} at {hackathon}: {award} on {date}";
+        assert_eq!(
+            fill(text, &certificate()),
+            "Asha Rao of Null Pointers at Smart India Hackathon: Winner on 07 March 2026"
+        );
+    }
+
+    #[test]
+    fn fill_replaces_repeats_and_leaves_unknown_braces() {
+        assert_eq!(
+            fill("{name}, {name} {unknown}", &certificate()),
+            "Asha Rao, Asha Rao {unknown}"
+        );
+    }
+}
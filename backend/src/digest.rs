@@ -0,0 +1,226 @@
+use crate::error::{AppError, AppResult};
+use crate::jobs;
+use crate::mailer::Email;
+use crate::models::{BlogPost, Hackathon, Job};
+use crate::AppState;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// Monday 03:30 UTC, i.e. 09:00 IST
+const DIGEST_HOUR_UTC: u32 = 3;
+const DIGEST_MINUTE_UTC: u32 = 30;
+const DEADLINE_HORIZON_DAYS: i32 = 14;
+const LOOKBACK_DAYS: i32 = 7;
+const SCHEDULE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DigestPayload {
+    week: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct DigestRecipient {
+    id: Uuid,
+    name: String,
+    email: String,
+    notify_submission: String,
+    notify_hackathon: String,
+    notify_blog: String,
+}
+
+// Makes sure the next digest job exists. Runs hourly; the dedupe key keeps
+// it to one digest per ISO week even across restarts and multiple servers.
+pub fn start_scheduler(state: AppState) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = schedule_next_digest(&state).await {
+                tracing::error!("Failed to schedule weekly digest: {}", e);
+            }
+            tokio::time::sleep(SCHEDULE_INTERVAL).await;
+        }
+    });
+}
+
+// First Monday slot at or after `now`. A server that starts mid-week waits
+// for the coming Monday rather than sending straight away.
+fn next_slot(now: DateTime<Utc>) -> AppResult<DateTime<Utc>> {
+    let monday = now.date_naive() - Duration::days(now.weekday().num_days_from_monday() as i64);
+    let slot = monday
+        .and_hms_opt(DIGEST_HOUR_UTC, DIGEST_MINUTE_UTC, 0)
+        .map(|naive| Utc.from_utc_datetime(&naive))
+        .ok_or_else(|| AppError::InternalError("Invalid digest time".to_string()))?;
+
+    Ok(if slot >= now {
+        slot
+    } else {
+        slot + Duration::weeks(1)
+    })
+}
+
+async fn schedule_next_digest(state: &AppState) -> AppResult<()> {
+    let slot = next_slot(Utc::now())?;
+    let iso_week = slot.iso_week();
+    let week = format!("{}-W{:02}", iso_week.year(), iso_week.week());
+
+    let mut conn = state.db.acquire().await?;
+    let scheduled = jobs::enqueue_unique(
+        &mut conn,
+        jobs::WEEKLY_DIGEST,
+        &DigestPayload { week: week.clone() },
+        &format!("weekly_digest:{}", week),
+        slot,
+    )
+    .await?;
+
+    if scheduled {
+        tracing::info!("Scheduled weekly digest for {}", week);
+    }
+
+    Ok(())
+}
+
+// Queues one digest email per student who has any category set to `digest`
+pub async fn send_weekly_digest(state: &AppState, job: &Job) -> AppResult<()> {
+    let payload: DigestPayload = serde_json::from_value(job.payload.clone())
+        .map_err(|e| AppError::InternalError(format!("Invalid job payload: {}", e)))?;
+
+    let hackathons: Vec<Hackathon> = sqlx::query_as(
+        "SELECT * FROM hackathons
+         WHERE status <> 'CLOSED'
+           AND registration_deadline BETWEEN NOW() AND NOW() + $1 * INTERVAL '1 day'
+         ORDER BY registration_deadline",
+    )
+    .bind(DEADLINE_HORIZON_DAYS)
+    .fetch_all(&state.db)
+    .await?;
+
+    let posts: Vec<BlogPost> = sqlx::query_as(
+        "SELECT * FROM blog_posts
+         WHERE status = 'published' AND created_at >= NOW() - $1 * INTERVAL '1 day'
+         ORDER BY created_at DESC",
+    )
+    .bind(LOOKBACK_DAYS)
+    .fetch_all(&state.db)
+    .await?;
+
+    let recipients: Vec<DigestRecipient> = sqlx::query_as(
+        "SELECT id, name, email, notify_submission, notify_hackathon, notify_blog
+         FROM students
+         WHERE 'digest' IN (notify_submission, notify_hackathon, notify_blog)",
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let updates: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT n.student_id, n.title
+         FROM notifications n
+         JOIN students st ON st.id = n.student_id
+         WHERE st.notify_submission = 'digest'
+           AND n.category = 'submission'
+           AND n.created_at >= NOW() - $1 * INTERVAL '1 day'
+         ORDER BY n.created_at",
+    )
+    .bind(LOOKBACK_DAYS)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut updates_by_student: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (student_id, title) in updates {
+        updates_by_student
+            .entry(student_id)
+            .or_default()
+            .push(title);
+    }
+
+    let frontend_url = state.frontend_url.trim_end_matches('/');
+    let mut tx = state.db.begin().await?;
+    let mut queued = 0;
+
+    for recipient in &recipients {
+        let mut sections = Vec::new();
+
+        if recipient.notify_submission == "digest" {
+            if let Some(titles) = updates_by_student.get(&recipient.id) {
+                let lines: Vec<String> = titles.iter().map(|t| format!("  - {}", t)).collect();
+                sections.push(format!("Your submissions:\n{}", lines.join("\n")));
+            }
+        }
+
+        if recipient.notify_hackathon == "digest" && !hackathons.is_empty() {
+            let lines: Vec<String> = hackathons
+                .iter()
+                .map(|h| {
+                    format!(
+                        "  - {} (registration closes {})\n    {}/hackathons/{}",
+                        h.name,
+                        h.registration_deadline.format("%d %b %Y"),
+                        frontend_url,
+                        h.slug.clone().unwrap_or_else(|| h.id.to_string())
+                    )
+                })
+                .collect();
+            sections.push(format!("Upcoming deadlines:\n{}", lines.join("\n")));
+        }
+
+        if recipient.notify_blog == "digest" && !posts.is_empty() {
+            let lines: Vec<String> = posts
+                .iter()
+                .map(|p| format!("  - {}\n    {}/blog/{}", p.title, frontend_url, p.slug))
+                .collect();
+            sections.push(format!("New posts:\n{}", lines.join("\n")));
+        }
+
+        if sections.is_empty() {
+            continue;
+        }
+
+        let email = Email {
+            to: recipient.email.clone(),
+            subject: "Your weekly COIN digest".to_string(),
+            body: format!(
+                "Hi {},\n\nHere is what happened this week.\n\n{}\n\n\
+                 You can change which emails you get in your notification preferences.\n\n\
+                 COIN SREC\n",
+                recipient.name,
+                sections.join("\n\n")
+            ),
+        };
+
+        // Keyed per student so a retried digest job doesn't email anyone twice
+        let dedupe_key = format!("digest:{}:{}", payload.week, recipient.id);
+        if jobs::enqueue_unique(&mut tx, jobs::SEND_EMAIL, &email, &dedupe_key, Utc::now()).await? {
+            queued += 1;
+        }
+    }
+
+    tx.commit().await?;
+    tracing::info!("Queued {} digest emails for {}", queued, payload.week);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // October 2026: the 19th and 26th are Mondays
+        Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn monday_before_the_slot_uses_the_same_day() {
+        assert_eq!(next_slot(at(19, 1, 0)).unwrap(), at(19, 3, 30));
+        assert_eq!(next_slot(at(19, 3, 30)).unwrap(), at(19, 3, 30));
+    }
+
+    #[test]
+    fn later_in_the_week_waits_for_next_monday() {
+        assert_eq!(next_slot(at(19, 3, 31)).unwrap(), at(26, 3, 30));
+        assert_eq!(next_slot(at(22, 12, 0)).unwrap(), at(26, 3, 30));
+        assert_eq!(next_slot(at(25, 23, 59)).unwrap(), at(26, 3, 30));
+    }
+}
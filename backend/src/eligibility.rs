@@ -0,0 +1,235 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{Hackathon, ParticipantInput};
+use crate::utils::academic_year_number;
+
+// Checks team rules an admin sets on a hackathon before they are saved
+pub fn validate_rules(
+    min_team_size: i32,
+    max_team_size: i32,
+    max_mentors: i32,
+    allowed_years: &[i32],
+) -> AppResult<()> {
+    if min_team_size < 1 {
+        return Err(AppError::BadRequest(
+            "Minimum team size must be at least 1".to_string(),
+        ));
+    }
+
+    if max_team_size < min_team_size {
+        return Err(AppError::BadRequest(
+            "Maximum team size cannot be less than the minimum team size".to_string(),
+        ));
+    }
+
+    if max_mentors < 0 {
+        return Err(AppError::BadRequest(
+            "Maximum mentors cannot be negative".to_string(),
+        ));
+    }
+
+    if allowed_years.iter().any(|year| !(1..=4).contains(year)) {
+        return Err(AppError::BadRequest(
+            "Allowed academic years must be between 1 and 4".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Enforces a hackathon's team size and eligibility rules on a submission
+pub fn check_team(
+    hackathon: &Hackathon,
+    participants: &[ParticipantInput],
+    mentor_count: usize,
+) -> AppResult<()> {
+    let team_size = participants.len() as i32;
+
+    if team_size < hackathon.min_team_size {
+        return Err(AppError::BadRequest(format!(
+            "{} requires at least {} participant(s) per team",
+            hackathon.name, hackathon.min_team_size
+        )));
+    }
+
+    if team_size > hackathon.max_team_size {
+        return Err(AppError::BadRequest(format!(
+            "{} allows at most {} participants per team",
+            hackathon.name, hackathon.max_team_size
+        )));
+    }
+
+    if mentor_count as i32 > hackathon.max_mentors {
+        return Err(AppError::BadRequest(format!(
+            "{} allows at most {} mentor(s) per team",
+            hackathon.name, hackathon.max_mentors
+        )));
+    }
+
+    for participant in participants {
+        if !hackathon.allowed_years.is_empty() {
+            let year = academic_year_number(&participant.academic_year).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "{} has an unrecognised academic year '{}'",
+                    participant.name, participant.academic_year
+                ))
+            })?;
+
+            if !hackathon.allowed_years.contains(&year) {
+                return Err(AppError::BadRequest(format!(
+                    "{} is not eligible: {} is open to year(s) {} only",
+                    participant.name,
+                    hackathon.name,
+                    join_years(&hackathon.allowed_years)
+                )));
+            }
+        }
+
+        if !hackathon.allowed_branches.is_empty() {
+            let department = participant.department.trim();
+            let allowed = hackathon
+                .allowed_branches
+                .iter()
+                .any(|branch| branch.trim().eq_ignore_ascii_case(department));
+
+            if !allowed {
+                return Err(AppError::BadRequest(format!(
+                    "{} is not eligible: {} is open to {} only",
+                    participant.name,
+                    hackathon.name,
+                    hackathon.allowed_branches.join(", ")
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn join_years(years: &[i32]) -> String {
+    let mut sorted = years.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .iter()
+        .map(|year| year.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    fn hackathon(min: i32, max: i32, years: &[i32], branches: &[&str]) -> Hackathon {
+        let now = Utc::now();
+        Hackathon {
+            id: Uuid::new_v4(),
+            name: "Hack".to_string(),
+            slug: None,
+            organizer: "SREC".to_string(),
+            description: String::new(),
+            mode: "OFFLINE".to_string(),
+            location: None,
+            start_date: now,
+            end_date: now,
+            registration_deadline: now,
+            official_registration_link: String::new(),
+            eligibility: String::new(),
+            status: "UPCOMING".to_string(),
+            semester: "2024-odd".to_string(),
+            created_at: now,
+            updated_at: now,
+            created_by: Uuid::new_v4(),
+            registration_fields: Json(Vec::new()),
+            min_team_size: min,
+            max_team_size: max,
+            max_mentors: 1,
+            allowed_years: years.to_vec(),
+            allowed_branches: branches.iter().map(|b| b.to_string()).collect(),
+            team_capacity: None,
+        }
+    }
+
+    fn participant(department: &str, year: &str) -> ParticipantInput {
+        ParticipantInput {
+            name: "Asha".to_string(),
+            email: "asha@srec.ac.in".to_string(),
+            department: department.to_string(),
+            academic_year: year.to_string(),
+        }
+    }
+
+    #[test]
+    fn accepts_sensible_rules() {
+        assert!(validate_rules(1, 4, 2, &[2, 3]).is_ok());
+        assert!(validate_rules(3, 3, 0, &[]).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_rule_shapes() {
+        assert!(validate_rules(0, 4, 1, &[]).is_err());
+        assert!(validate_rules(4, 2, 1, &[]).is_err());
+        assert!(validate_rules(1, 4, -1, &[]).is_err());
+        assert!(validate_rules(1, 4, 1, &[0]).is_err());
+        assert!(validate_rules(1, 4, 1, &[1, 5]).is_err());
+    }
+
+    #[test]
+    fn enforces_team_size_limits() {
+        let h = hackathon(2, 3, &[], &[]);
+        let team = |size: usize| -> Vec<ParticipantInput> {
+            (0..size).map(|_| participant("CSE", "2nd Year")).collect()
+        };
+
+        assert!(check_team(&h, &team(1), 0).is_err());
+        assert!(check_team(&h, &team(2), 0).is_ok());
+        assert!(check_team(&h, &team(3), 1).is_ok());
+        assert!(check_team(&h, &team(4), 0).is_err());
+        assert!(check_team(&h, &team(2), 2).is_err());
+    }
+
+    #[test]
+    fn empty_allow_lists_admit_everyone() {
+        let h = hackathon(1, 4, &[], &[]);
+        let team = [
+            participant("Mech", "First Year"),
+            participant("", "unknown"),
+        ];
+
+        assert!(check_team(&h, &team, 0).is_ok());
+    }
+
+    #[test]
+    fn enforces_allowed_years() {
+        let h = hackathon(1, 4, &[3, 4], &[]);
+
+        assert!(check_team(&h, &[participant("CSE", "3rd Year")], 0).is_ok());
+        assert!(check_team(&h, &[participant("CSE", "IV")], 0).is_ok());
+        assert!(check_team(&h, &[participant("CSE", "2nd Year")], 0).is_err());
+        assert!(check_team(
+            &h,
+            &[participant("CSE", "3rd Year"), participant("CSE", "first")],
+            0
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_unparseable_years_when_years_are_restricted() {
+        let h = hackathon(1, 4, &[1, 2, 3, 4], &[]);
+
+        assert!(check_team(&h, &[participant("CSE", "Final Year")], 0).is_err());
+        assert!(check_team(&h, &[participant("CSE", "")], 0).is_err());
+    }
+
+    #[test]
+    fn matches_allowed_departments_loosely() {
+        let h = hackathon(1, 4, &[], &["CSE", " IT "]);
+
+        assert!(check_team(&h, &[participant("cse", "1")], 0).is_ok());
+        assert!(check_team(&h, &[participant(" it", "1")], 0).is_ok());
+        assert!(check_team(&h, &[participant("ECE", "1")], 0).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error::AppResult;
+use crate::live;
+use crate::models::{BlogPost, Hackathon, Mentor, Submission};
+use crate::notifications;
+use crate::webhooks;
+use serde_json::json;
+use sqlx::PgConnection;
+
+// Domain events. Each one fans out to student notifications, webhooks and the
+// live admin dashboard on the caller's connection, so they only take effect if
+// the change commits.
+
+pub async fn hackathon_created(conn: &mut PgConnection, hackathon: &Hackathon) -> AppResult<()> {
+    notifications::hackathon_created(conn, hackathon).await?;
+    webhooks::dispatch(conn, "hackathon.created", json!(hackathon)).await
+}
+
+pub async fn submission_created(conn: &mut PgConnection, submission: &Submission) -> AppResult<()> {
+    live::notify_submission(conn, "submission.created", submission, None).await?;
+    webhooks::dispatch(conn, "submission.created", json!(submission)).await
+}
+
+pub async fn submission_status_changed(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    previous_status: &str,
+) -> AppResult<()> {
+    notifications::submission_status_changed(conn, submission).await?;
+    live::notify_submission(
+        conn,
+        "submission.status_changed",
+        submission,
+        Some(previous_status),
+    )
+    .await?;
+    webhooks::dispatch(
+        conn,
+        "submission.status_changed",
+        json!({
+            "submission": submission,
+            "previous_status": previous_status,
+        }),
+    )
+    .await
+}
+
+pub async fn mentorship_requested(conn: &mut PgConnection, mentor: &Mentor) -> AppResult<()> {
+    notifications::mentorship_requested(conn, mentor).await
+}
+
+pub async fn mentorship_responded(conn: &mut PgConnection, mentor: &Mentor) -> AppResult<()> {
+    notifications::mentorship_responded(conn, mentor).await
+}
+
+pub async fn blog_post_published(conn: &mut PgConnection, post: &BlogPost) -> AppResult<()> {
+    notifications::blog_post_published(conn, post).await?;
+    webhooks::dispatch(conn, "blog.published", json!(post)).await
+}
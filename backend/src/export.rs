@@ -1,59 +1,230 @@
 use crate::error::{AppError, AppResult};
-use crate::models::SubmissionDetail;
+use crate::forms::format_answers;
+use crate::models::{MentorReport, SubmissionDetail};
 use csv::Writer;
+use std::collections::HashMap;
 
-fn get_headers() -> Vec<&'static str> {
-    vec![
-        "submission_id",
-        "submitted_at",
-        "semester",
-        "hackathon_name",
-        "team_name",
-        "participant_count",
-        "mentor_count",
-        "participant_name",
-        "participant_email",
-        "participant_department",
-        "participant_year",
-        "mentor_names",
-        "mentor_departments",
-        "external_confirmed",
-        "status",
-    ]
+// Columns callers can pick from, in the default export order
+pub const EXPORT_COLUMNS: [&str; 21] = [
+    "submission_id",
+    "submitted_at",
+    "semester",
+    "hackathon_name",
+    "team_name",
+    "participant_count",
+    "mentor_count",
+    "participant_name",
+    "participant_email",
+    "participant_department",
+    "participant_year",
+    "mentor_names",
+    "mentor_departments",
+    "external_confirmed",
+    "status",
+    "result_outcome",
+    "result_rank",
+    "award_title",
+    "prize_amount",
+    "certificate_ref",
+    "answers",
+];
+
+// "participant" writes one row per team member, "team" one row per submission
+pub const EXPORT_LAYOUTS: [&str; 2] = ["participant", "team"];
+
+// Columns that differ between members of a team; joined in the team layout
+const PARTICIPANT_COLUMNS: [&str; 4] = [
+    "participant_name",
+    "participant_email",
+    "participant_department",
+    "participant_year",
+];
+
+pub const EXPORT_FORMATS: [&str; 2] = ["csv", "xlsx"];
+
+pub fn default_columns() -> Vec<String> {
+    EXPORT_COLUMNS.iter().map(|c| c.to_string()).collect()
 }
 
-fn get_submission_row(submission: &SubmissionDetail) -> Vec<String> {
-    vec![
-        submission.submission_id.to_string(),
-        submission.submitted_at.to_rfc3339(),
-        submission.semester.clone(),
-        submission.hackathon_name.clone(),
-        submission.team_name.clone(),
-        submission.participant_count.to_string(),
-        submission.mentor_count.to_string(),
-        submission.participant_name.clone().unwrap_or_default(),
-        submission.participant_email.clone().unwrap_or_default(),
-        submission
-            .participant_department
-            .clone()
-            .unwrap_or_default(),
-        submission.participant_year.clone().unwrap_or_default(),
-        submission.mentor_names.clone().unwrap_or_default(),
-        submission.mentor_departments.clone().unwrap_or_default(),
-        submission.external_confirmed.to_string(),
-        submission.status.clone(),
-    ]
+// Accepts `columns` as a comma-separated query value
+pub fn parse_columns(columns: &str) -> Vec<String> {
+    columns
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn validate_columns(columns: &[String]) -> AppResult<()> {
+    if columns.is_empty() {
+        return Err(AppError::BadRequest(
+            "Select at least one column".to_string(),
+        ));
+    }
+
+    if let Some(unknown) = columns
+        .iter()
+        .find(|c| !EXPORT_COLUMNS.contains(&c.as_str()))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Unknown export column: {}. Use any of: {}",
+            unknown,
+            EXPORT_COLUMNS.join(", ")
+        )));
+    }
+
+    if let Some((i, _)) = columns
+        .iter()
+        .enumerate()
+        .find(|(i, c)| columns[..*i].contains(c))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Export column listed twice: {}",
+            columns[i]
+        )));
+    }
+
+    Ok(())
 }
 
-pub fn generate_csv(submissions: Vec<SubmissionDetail>) -> AppResult<Vec<u8>> {
+pub fn validate_layout(layout: &str) -> AppResult<()> {
+    if !EXPORT_LAYOUTS.contains(&layout) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid export layout. Use one of: {}",
+            EXPORT_LAYOUTS.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_format(format: &str) -> AppResult<()> {
+    if !EXPORT_FORMATS.contains(&format) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid export format. Use one of: {}",
+            EXPORT_FORMATS.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+enum Cell {
+    Text(String),
+    Integer(i64),
+    Bool(bool),
+    Empty,
+}
+
+impl Cell {
+    fn text(value: Option<&str>) -> Cell {
+        value.map_or(Cell::Empty, |v| Cell::Text(v.to_string()))
+    }
+
+    fn as_string(&self) -> String {
+        match self {
+            Cell::Text(value) => value.clone(),
+            Cell::Integer(value) => value.to_string(),
+            Cell::Bool(value) => value.to_string(),
+            Cell::Empty => String::new(),
+        }
+    }
+}
+
+// Columns are validated before export, so unknown names never reach here
+fn cell(column: &str, submission: &SubmissionDetail) -> Cell {
+    match column {
+        "submission_id" => Cell::Text(submission.submission_id.to_string()),
+        "submitted_at" => Cell::Text(submission.submitted_at.to_rfc3339()),
+        "semester" => Cell::Text(submission.semester.clone()),
+        "hackathon_name" => Cell::Text(submission.hackathon_name.clone()),
+        "team_name" => Cell::Text(submission.team_name.clone()),
+        "participant_count" => Cell::Integer(submission.participant_count.into()),
+        "mentor_count" => Cell::Integer(submission.mentor_count.into()),
+        "participant_name" => Cell::text(submission.participant_name.as_deref()),
+        "participant_email" => Cell::text(submission.participant_email.as_deref()),
+        "participant_department" => Cell::text(submission.participant_department.as_deref()),
+        "participant_year" => Cell::text(submission.participant_year.as_deref()),
+        "mentor_names" => Cell::text(submission.mentor_names.as_deref()),
+        "mentor_departments" => Cell::text(submission.mentor_departments.as_deref()),
+        "external_confirmed" => Cell::Bool(submission.external_confirmed),
+        "status" => Cell::Text(submission.status.clone()),
+        "result_outcome" => Cell::text(submission.result_outcome.as_deref()),
+        "result_rank" => submission
+            .result_rank
+            .map_or(Cell::Empty, |rank| Cell::Integer(rank.into())),
+        "award_title" => Cell::text(submission.award_title.as_deref()),
+        "prize_amount" => submission.prize_amount.map_or(Cell::Empty, Cell::Integer),
+        "certificate_ref" => Cell::text(submission.certificate_ref.as_deref()),
+        "answers" => Cell::Text(format_answers(&submission.answers)),
+        _ => Cell::Empty,
+    }
+}
+
+// Submission details come back one row per participant. The team layout
+// folds them into one row per submission, keeping the query's order.
+fn build_rows(
+    submissions: &[SubmissionDetail],
+    columns: &[String],
+    layout: &str,
+) -> Vec<Vec<Cell>> {
+    if layout != "team" {
+        return submissions
+            .iter()
+            .map(|submission| columns.iter().map(|c| cell(c, submission)).collect())
+            .collect();
+    }
+
+    let mut teams: Vec<Vec<&SubmissionDetail>> = Vec::new();
+    let mut index: HashMap<_, usize> = HashMap::new();
+    for submission in submissions {
+        match index.get(&submission.submission_id) {
+            Some(&i) => teams[i].push(submission),
+            None => {
+                index.insert(submission.submission_id, teams.len());
+                teams.push(vec![submission]);
+            }
+        }
+    }
+
+    teams
+        .iter()
+        .map(|members| {
+            columns
+                .iter()
+                .map(|column| {
+                    if PARTICIPANT_COLUMNS.contains(&column.as_str()) {
+                        let values: Vec<String> = members
+                            .iter()
+                            .map(|m| cell(column, m).as_string())
+                            .filter(|v| !v.is_empty())
+                            .collect();
+                        if values.is_empty() {
+                            Cell::Empty
+                        } else {
+                            Cell::Text(values.join("; "))
+                        }
+                    } else {
+                        cell(column, members[0])
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn generate_csv(
+    submissions: &[SubmissionDetail],
+    columns: &[String],
+    layout: &str,
+) -> AppResult<Vec<u8>> {
     let mut wtr = Writer::from_writer(vec![]);
 
     // Write header
-    wtr.write_record(get_headers())
+    wtr.write_record(columns)
         .map_err(|e| AppError::InternalError(format!("CSV write error: {}", e)))?;
 
-    for submission in submissions {
-        wtr.write_record(get_submission_row(&submission))
+    for row in build_rows(submissions, columns, layout) {
+        wtr.write_record(row.iter().map(Cell::as_string))
             .map_err(|e| AppError::InternalError(format!("CSV write error: {}", e)))?;
     }
 
@@ -65,9 +236,16 @@ pub fn generate_csv(submissions: Vec<SubmissionDetail>) -> AppResult<Vec<u8>> {
         .map_err(|e| AppError::InternalError(format!("CSV extraction error: {}", e)))?)
 }
 
-pub fn generate_xlsx(submissions: Vec<SubmissionDetail>) -> AppResult<Vec<u8>> {
+pub fn generate_xlsx(
+    submissions: &[SubmissionDetail],
+    columns: &[String],
+    layout: &str,
+) -> AppResult<Vec<u8>> {
     use rust_xlsxwriter::{Format, Workbook};
 
+    let xlsx_error =
+        |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("XLSX error: {}", e));
+
     let mut workbook = Workbook::new();
     let worksheet = workbook.add_worksheet();
 
@@ -75,99 +253,307 @@ pub fn generate_xlsx(submissions: Vec<SubmissionDetail>) -> AppResult<Vec<u8>> {
     let header_format = Format::new().set_bold().set_background_color("#F3F4F6");
 
     // Write headers
-    let headers = get_headers();
-    for (col, header) in headers.iter().enumerate() {
+    for (col, header) in columns.iter().enumerate() {
         worksheet
-            .write_string_with_format(0, col as u16, *header, &header_format)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string_with_format(0, col as u16, header, &header_format)
+            .map_err(xlsx_error)?;
     }
 
     // Write data
-    for (row, submission) in submissions.iter().enumerate() {
+    for (row, cells) in build_rows(submissions, columns, layout).iter().enumerate() {
         let row_idx = (row + 1) as u32;
+        for (col, value) in cells.iter().enumerate() {
+            let col = col as u16;
+            match value {
+                Cell::Text(text) => worksheet.write_string(row_idx, col, text).map(|_| ()),
+                Cell::Integer(number) => worksheet
+                    .write_number(row_idx, col, *number as f64)
+                    .map(|_| ()),
+                Cell::Bool(flag) => worksheet.write_boolean(row_idx, col, *flag).map(|_| ()),
+                Cell::Empty => Ok(()),
+            }
+            .map_err(xlsx_error)?;
+        }
+    }
+
+    // Set column widths for better readability
+    for (col, column) in columns.iter().enumerate() {
+        let width = match column.as_str() {
+            "hackathon_name" => 30,
+            "team_name" => 25,
+            "participant_email" => 35,
+            _ => continue,
+        };
         worksheet
-            .write_string(row_idx, 0, &submission.submission_id.to_string())
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
-        worksheet
-            .write_string(row_idx, 1, &submission.submitted_at.to_rfc3339())
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
-        worksheet
-            .write_string(row_idx, 2, &submission.semester)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
-        worksheet
-            .write_string(row_idx, 3, &submission.hackathon_name)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .set_column_width(col as u16, width)
+            .map_err(xlsx_error)?;
+    }
+
+    workbook
+        .save_to_buffer()
+        .map_err(|e| AppError::InternalError(format!("XLSX save error: {}", e)))
+}
+
+const MENTOR_HEADERS: [&str; 5] = [
+    "mentor_name",
+    "department",
+    "semester",
+    "teams",
+    "hackathons",
+];
+const MENTOR_DEPARTMENT_HEADERS: [&str; 4] = ["department", "semester", "mentors", "teams"];
+
+// CSV holds the per-mentor table; the department summary is in the XLSX version
+pub fn generate_mentor_report_csv(report: &MentorReport) -> AppResult<Vec<u8>> {
+    let mut wtr = Writer::from_writer(vec![]);
+
+    wtr.write_record(MENTOR_HEADERS)
+        .map_err(|e| AppError::InternalError(format!("CSV write error: {}", e)))?;
+
+    for mentor in &report.mentors {
+        wtr.write_record([
+            mentor.name.clone(),
+            mentor.department.clone(),
+            mentor.semester.clone(),
+            mentor.teams.to_string(),
+            mentor.hackathons.to_string(),
+        ])
+        .map_err(|e| AppError::InternalError(format!("CSV write error: {}", e)))?;
+    }
+
+    wtr.flush()
+        .map_err(|e| AppError::InternalError(format!("CSV flush error: {}", e)))?;
+
+    wtr.into_inner()
+        .map_err(|e| AppError::InternalError(format!("CSV extraction error: {}", e)))
+}
+
+pub fn generate_mentor_report_xlsx(report: &MentorReport) -> AppResult<Vec<u8>> {
+    use rust_xlsxwriter::{Format, Workbook};
+
+    let xlsx_error =
+        |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("XLSX error: {}", e));
+
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold().set_background_color("#F3F4F6");
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Mentors").map_err(xlsx_error)?;
+    for (col, header) in MENTOR_HEADERS.iter().enumerate() {
         worksheet
-            .write_string(row_idx, 4, &submission.team_name)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string_with_format(0, col as u16, *header, &header_format)
+            .map_err(xlsx_error)?;
+    }
+    for (row, mentor) in report.mentors.iter().enumerate() {
+        let row_idx = (row + 1) as u32;
         worksheet
-            .write_number(row_idx, 5, submission.participant_count as f64)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string(row_idx, 0, &mentor.name)
+            .map_err(xlsx_error)?;
         worksheet
-            .write_number(row_idx, 6, submission.mentor_count as f64)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string(row_idx, 1, &mentor.department)
+            .map_err(xlsx_error)?;
         worksheet
-            .write_string(
-                row_idx,
-                7,
-                submission.participant_name.as_deref().unwrap_or(""),
-            )
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string(row_idx, 2, &mentor.semester)
+            .map_err(xlsx_error)?;
         worksheet
-            .write_string(
-                row_idx,
-                8,
-                submission.participant_email.as_deref().unwrap_or(""),
-            )
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_number(row_idx, 3, mentor.teams as f64)
+            .map_err(xlsx_error)?;
         worksheet
-            .write_string(
-                row_idx,
-                9,
-                submission.participant_department.as_deref().unwrap_or(""),
-            )
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_number(row_idx, 4, mentor.hackathons as f64)
+            .map_err(xlsx_error)?;
+    }
+    worksheet.set_column_width(0, 30).map_err(xlsx_error)?;
+    worksheet.set_column_width(1, 40).map_err(xlsx_error)?;
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Departments").map_err(xlsx_error)?;
+    for (col, header) in MENTOR_DEPARTMENT_HEADERS.iter().enumerate() {
         worksheet
-            .write_string(
-                row_idx,
-                10,
-                submission.participant_year.as_deref().unwrap_or(""),
-            )
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string_with_format(0, col as u16, *header, &header_format)
+            .map_err(xlsx_error)?;
+    }
+    for (row, department) in report.departments.iter().enumerate() {
+        let row_idx = (row + 1) as u32;
         worksheet
-            .write_string(
-                row_idx,
-                11,
-                submission.mentor_names.as_deref().unwrap_or(""),
-            )
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string(row_idx, 0, &department.department)
+            .map_err(xlsx_error)?;
         worksheet
-            .write_string(
-                row_idx,
-                12,
-                submission.mentor_departments.as_deref().unwrap_or(""),
-            )
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_string(row_idx, 1, &department.semester)
+            .map_err(xlsx_error)?;
         worksheet
-            .write_boolean(row_idx, 13, submission.external_confirmed)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_number(row_idx, 2, department.mentors as f64)
+            .map_err(xlsx_error)?;
         worksheet
-            .write_string(row_idx, 14, &submission.status)
-            .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+            .write_number(row_idx, 3, department.teams as f64)
+            .map_err(xlsx_error)?;
     }
-
-    // Set column widths for better readability
-    worksheet
-        .set_column_width(3, 30)
-        .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
-    worksheet
-        .set_column_width(4, 25)
-        .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
-    worksheet
-        .set_column_width(8, 35)
-        .map_err(|e| AppError::InternalError(format!("XLSX error: {}", e)))?;
+    worksheet.set_column_width(0, 40).map_err(xlsx_error)?;
 
     workbook
         .save_to_buffer()
         .map_err(|e| AppError::InternalError(format!("XLSX save error: {}", e)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|c| c.to_string()).collect()
+    }
+
+    fn row(
+        submission_id: Uuid,
+        team_name: &str,
+        participant: Option<(&str, &str)>,
+    ) -> SubmissionDetail {
+        SubmissionDetail {
+            submission_id,
+            submitted_at: Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap(),
+            semester: "2025-26 Even".to_string(),
+            hackathon_name: "Smart India Hackathon".to_string(),
+            team_name: team_name.to_string(),
+            participant_count: 2,
+            mentor_count: 0,
+            participant_name: participant.map(|(name, _)| name.to_string()),
+            participant_email: participant.map(|(_, email)| email.to_string()),
+            participant_department: None,
+            participant_year: None,
+            mentor_names: None,
+            mentor_departments: None,
+            external_confirmed: true,
+            status: "verified".to_string(),
+            result_outcome: None,
+            result_rank: None,
+            award_title: None,
+            prize_amount: None,
+            certificate_ref: None,
+            answers: json!({}),
+        }
+    }
+
+    fn csv_lines(submissions: &[SubmissionDetail], cols: &[&str], layout: &str) -> Vec<String> {
+        let data = generate_csv(submissions, &columns(cols), layout).unwrap();
+        String::from_utf8(data)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn parse_columns_trims_and_skips_empty_entries() {
+        assert_eq!(
+            parse_columns(" team_name, ,status,"),
+            columns(&["team_name", "status"])
+        );
+    }
+
+    #[test]
+    fn columns_are_written_in_the_requested_order() {
+        let rows = [row(
+            Uuid::new_v4(),
+            "Null Pointers",
+            Some(("Asha", "asha@srec.ac.in")),
+        )];
+        let lines = csv_lines(
+            &rows,
+            &["status", "participant_name", "team_name"],
+            "participant",
+        );
+        assert_eq!(
+            lines,
+            [
+                "status,participant_name,team_name",
+                "verified,Asha,Null Pointers"
+            ]
+        );
+    }
+
+    #[test]
+    fn default_columns_follow_the_export_column_list() {
+        assert_eq!(default_columns(), columns(&EXPORT_COLUMNS));
+        assert!(validate_columns(&default_columns()).is_ok());
+    }
+
+    #[test]
+    fn unknown_and_duplicate_columns_are_rejected() {
+        assert!(validate_columns(&[]).is_err());
+        let unknown = validate_columns(&columns(&["team_name", "password_hash"]));
+        assert!(matches!(unknown, Err(AppError::BadRequest(m)) if m.contains("password_hash")));
+        let duplicate = validate_columns(&columns(&["team_name", "status", "team_name"]));
+        assert!(matches!(duplicate, Err(AppError::BadRequest(m)) if m.contains("listed twice")));
+    }
+
+    #[test]
+    fn layouts_and_formats_are_validated() {
+        assert!(validate_layout("team").is_ok());
+        assert!(validate_layout("mentor").is_err());
+        assert!(validate_format("xlsx").is_ok());
+        assert!(validate_format("pdf").is_err());
+    }
+
+    #[test]
+    fn participant_layout_writes_one_row_per_member() {
+        let id = Uuid::new_v4();
+        let rows = [
+            row(id, "Null Pointers", Some(("Asha", "asha@srec.ac.in"))),
+            row(id, "Null Pointers", Some(("Ravi", "ravi@srec.ac.in"))),
+        ];
+        let lines = csv_lines(&rows, &["team_name", "participant_name"], "participant");
+        assert_eq!(
+            lines,
+            [
+                "team_name,participant_name",
+                "Null Pointers,Asha",
+                "Null Pointers,Ravi"
+            ]
+        );
+    }
+
+    #[test]
+    fn team_layout_joins_members_and_keeps_query_order() {
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let rows = [
+            row(first, "Null Pointers", Some(("Asha", "asha@srec.ac.in"))),
+            row(second, "Segfaults", Some(("Meena", "meena@srec.ac.in"))),
+            row(first, "Null Pointers", Some(("Ravi", "ravi@srec.ac.in"))),
+            // A team whose participant rows are missing still gets a row
+            row(Uuid::new_v4(), "Ghosts", None),
+        ];
+        let lines = csv_lines(
+            &rows,
+            &[
+                "team_name",
+                "participant_name",
+                "participant_email",
+                "status",
+            ],
+            "team",
+        );
+        assert_eq!(
+            lines,
+            [
+                "team_name,participant_name,participant_email,status",
+                "Null Pointers,Asha; Ravi,asha@srec.ac.in; ravi@srec.ac.in,verified",
+                "Segfaults,Meena,meena@srec.ac.in,verified",
+                "Ghosts,,,verified",
+            ]
+        );
+    }
+
+    #[test]
+    fn xlsx_export_builds_a_workbook() {
+        let rows = [row(
+            Uuid::new_v4(),
+            "Null Pointers",
+            Some(("Asha", "asha@srec.ac.in")),
+        )];
+        let data = generate_xlsx(&rows, &default_columns(), "team").unwrap();
+        // XLSX files are ZIP archives
+        assert!(data.starts_with(b"PK\x03\x04"));
+    }
+}
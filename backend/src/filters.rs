@@ -0,0 +1,370 @@
+use crate::error::{AppError, AppResult};
+use crate::utils::{generate_slug, sanitize_file_name};
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+// Submission filters shared by admin handlers. Conditions are written against
+// `submissions s JOIN hackathons h ON s.hackathon_id = h.id`.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct SubmissionFilter {
+    pub hackathon_id: Option<String>,
+    pub status: Option<String>,
+    pub semester: Option<String>,
+    pub department: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub q: Option<String>,
+}
+
+impl SubmissionFilter {
+    pub fn is_empty(&self) -> bool {
+        self.hackathon_id.is_none()
+            && self.status.is_none()
+            && self.semester.is_none()
+            && self.department.is_none()
+            && self.from.is_none()
+            && self.to.is_none()
+            && self.search_term().is_none()
+    }
+
+    fn search_term(&self) -> Option<&str> {
+        self.q.as_deref().map(str::trim).filter(|q| !q.is_empty())
+    }
+
+    pub fn hackathon_uuid(&self) -> AppResult<Option<Uuid>> {
+        self.hackathon_id
+            .as_deref()
+            .map(|hid| {
+                Uuid::parse_str(hid)
+                    .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))
+            })
+            .transpose()
+    }
+
+    pub fn push_conditions(&self, qb: &mut QueryBuilder<'_, Postgres>) -> AppResult<()> {
+        if let Some(hackathon_id) = self.hackathon_uuid()? {
+            qb.push(" AND s.hackathon_id = ").push_bind(hackathon_id);
+        }
+
+        if let Some(status) = &self.status {
+            qb.push(" AND s.status = ").push_bind(status.clone());
+        }
+
+        if let Some(semester) = &self.semester {
+            qb.push(" AND h.semester = ").push_bind(semester.clone());
+        }
+
+        // Departments match ignoring case and surrounding spaces, as in the analytics
+        if let Some(department) = &self.department {
+            qb.push(
+                " AND EXISTS (SELECT 1 FROM participants fp WHERE fp.submission_id = s.id AND LOWER(TRIM(fp.department)) = LOWER(TRIM(",
+            )
+            .push_bind(department.clone())
+            .push(")))");
+        }
+
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err(AppError::BadRequest(
+                    "'from' date must not be after 'to' date".to_string(),
+                ));
+            }
+        }
+
+        if let Some(from) = self.from {
+            qb.push(" AND s.created_at >= ").push_bind(from);
+        }
+
+        // `to` is inclusive, so compare against the start of the following day
+        if let Some(to) = self.to {
+            qb.push(" AND s.created_at < ").push_bind(to + Duration::days(1));
+        }
+
+        if let Some(term) = self.search_term() {
+            let pattern = format!("%{}%", escape_like(term));
+            qb.push(" AND (s.team_name ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR EXISTS (SELECT 1 FROM participants sp WHERE sp.submission_id = s.id AND (sp.name ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR sp.email ILIKE ")
+                .push_bind(pattern.clone())
+                .push(")) OR EXISTS (SELECT 1 FROM mentors sm WHERE sm.submission_id = s.id AND sm.name ILIKE ")
+                .push_bind(pattern)
+                .push("))");
+        }
+
+        Ok(())
+    }
+
+    // Download name describing what was exported, e.g.
+    // `submissions_2024-odd_smart-india-hackathon_verified_2024-10-19.csv`.
+    // The search term is left out since it is rarely meaningful out of context.
+    pub fn export_file_name(
+        &self,
+        prefix: &str,
+        hackathon_name: Option<&str>,
+        exported_on: NaiveDate,
+        extension: &str,
+    ) -> String {
+        let mut parts = vec![prefix.to_string()];
+        parts.extend(
+            [
+                self.semester.as_deref(),
+                hackathon_name,
+                self.status.as_deref(),
+                self.department.as_deref(),
+            ]
+            .into_iter()
+            .flatten()
+            .map(generate_slug)
+            .filter(|part| !part.is_empty()),
+        );
+
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => parts.push(format!("{}-to-{}", from, to)),
+            (Some(from), None) => parts.push(format!("from-{}", from)),
+            (None, Some(to)) => parts.push(format!("to-{}", to)),
+            (None, None) => {}
+        }
+
+        parts.push(exported_on.to_string());
+
+        sanitize_file_name(&format!("{}.{}", parts.join("_"), extension))
+    }
+}
+
+// Columns the admin submission list can be sorted by, mapped to SQL expressions
+// so user input never reaches the query text.
+pub fn submission_sort_column(sort: Option<&str>) -> AppResult<&'static str> {
+    match sort.unwrap_or("created_at") {
+        "created_at" => Ok("s.created_at"),
+        "team_name" => Ok("LOWER(s.team_name)"),
+        "participant_count" => Ok("s.participant_count"),
+        "mentor_count" => Ok("s.mentor_count"),
+        "status" => Ok("s.status"),
+        "hackathon_name" => Ok("LOWER(h.name)"),
+        other => Err(AppError::BadRequest(format!(
+            "Invalid sort field: {}",
+            other
+        ))),
+    }
+}
+
+pub fn sort_direction(order: Option<&str>) -> AppResult<&'static str> {
+    match order.unwrap_or("desc") {
+        "asc" => Ok("ASC"),
+        "desc" => Ok("DESC"),
+        other => Err(AppError::BadRequest(format!(
+            "Invalid sort order: {}",
+            other
+        ))),
+    }
+}
+
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_SQL: &str =
+        "SELECT s.id FROM submissions s JOIN hackathons h ON s.hackathon_id = h.id WHERE 1=1";
+    const HACKATHON_ID: &str = "6f1c2a52-8d8e-4d6a-9a4b-3c1f3e9b7a10";
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    // Bit i of `mask` turns on the i-th filter field
+    fn filter_for(mask: u32) -> SubmissionFilter {
+        let on = |bit: u32| mask & (1 << bit) != 0;
+        SubmissionFilter {
+            hackathon_id: on(0).then(|| HACKATHON_ID.to_string()),
+            status: on(1).then(|| "verified".to_string()),
+            semester: on(2).then(|| "2024-Odd".to_string()),
+            department: on(3).then(|| "CSE".to_string()),
+            from: on(4).then(|| date(2024, 8, 1)),
+            to: on(5).then(|| date(2024, 11, 30)),
+            q: on(6).then(|| "robo".to_string()),
+        }
+    }
+
+    fn build(filter: &SubmissionFilter) -> AppResult<String> {
+        let mut qb = QueryBuilder::<Postgres>::new(BASE_SQL);
+        filter.push_conditions(&mut qb)?;
+        Ok(qb.sql().to_string())
+    }
+
+    fn placeholders(sql: &str) -> Vec<usize> {
+        sql.split('$')
+            .skip(1)
+            .map(|rest| {
+                let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+                digits.parse().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_filter_combination_builds_matching_conditions() {
+        let fragments: [(&str, usize); 7] = [
+            ("s.hackathon_id = ", 1),
+            ("s.status = ", 1),
+            ("h.semester = ", 1),
+            ("FROM participants fp", 1),
+            ("s.created_at >= ", 1),
+            ("s.created_at < ", 1),
+            ("s.team_name ILIKE ", 4),
+        ];
+
+        for mask in 0..(1u32 << fragments.len()) {
+            let filter = filter_for(mask);
+            let sql = build(&filter).unwrap();
+            let conditions = sql.strip_prefix(BASE_SQL).unwrap();
+
+            let mut expected_binds = 0;
+            for (bit, (fragment, binds)) in fragments.iter().enumerate() {
+                let enabled = mask & (1 << bit) != 0;
+                assert_eq!(
+                    conditions.contains(fragment),
+                    enabled,
+                    "mask {:07b}: {}",
+                    mask,
+                    fragment
+                );
+                if enabled {
+                    expected_binds += binds;
+                }
+            }
+
+            // Parameters are numbered in order with no gaps or reuse
+            let numbers = placeholders(&sql);
+            assert_eq!(numbers, (1..=expected_binds).collect::<Vec<_>>());
+            assert_eq!(filter.is_empty(), mask == 0);
+        }
+    }
+
+    #[test]
+    fn department_matches_ignoring_case_and_spaces() {
+        let filter = SubmissionFilter {
+            department: Some(" cSe ".to_string()),
+            ..Default::default()
+        };
+        let sql = build(&filter).unwrap();
+        assert!(sql.contains("LOWER(TRIM(fp.department)) = LOWER(TRIM($1))"));
+        assert!(!sql.contains("fp.department = $1"));
+    }
+
+    #[test]
+    fn invalid_hackathon_id_is_rejected() {
+        let filter = SubmissionFilter {
+            hackathon_id: Some("not-a-uuid".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(build(&filter), Err(AppError::BadRequest(_))));
+        assert!(filter.hackathon_uuid().is_err());
+    }
+
+    #[test]
+    fn reversed_date_range_is_rejected() {
+        let filter = SubmissionFilter {
+            from: Some(date(2024, 12, 1)),
+            to: Some(date(2024, 11, 1)),
+            ..Default::default()
+        };
+        assert!(matches!(build(&filter), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn single_day_range_is_allowed() {
+        let filter = SubmissionFilter {
+            from: Some(date(2024, 11, 1)),
+            to: Some(date(2024, 11, 1)),
+            ..Default::default()
+        };
+        let sql = build(&filter).unwrap();
+        assert!(sql.contains("s.created_at >= $1 AND s.created_at < $2"));
+    }
+
+    #[test]
+    fn blank_search_term_is_ignored() {
+        let filter = SubmissionFilter {
+            q: Some("   ".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.is_empty());
+        assert_eq!(build(&filter).unwrap(), BASE_SQL);
+    }
+
+    #[test]
+    fn like_wildcards_are_escaped() {
+        assert_eq!(escape_like(r"50%_off\"), r"50\%\_off\\");
+    }
+
+    #[test]
+    fn export_file_name_without_filters() {
+        let name = SubmissionFilter::default().export_file_name(
+            "submissions",
+            None,
+            date(2024, 10, 19),
+            "csv",
+        );
+        assert_eq!(name, "submissions_2024-10-19.csv");
+    }
+
+    #[test]
+    fn export_file_name_describes_filters() {
+        let filter = filter_for(u32::MAX);
+        let name = filter.export_file_name(
+            "submissions",
+            Some("Smart India Hackathon 2024"),
+            date(2024, 12, 2),
+            "xlsx",
+        );
+        assert_eq!(
+            name,
+            "submissions_2024-odd_smart-india-hackathon-2024_verified_cse_2024-08-01-to-2024-11-30_2024-12-02.xlsx"
+        );
+    }
+
+    #[test]
+    fn export_file_name_with_open_date_range() {
+        let from_only = SubmissionFilter {
+            from: Some(date(2024, 8, 1)),
+            ..Default::default()
+        };
+        let to_only = SubmissionFilter {
+            to: Some(date(2024, 11, 30)),
+            ..Default::default()
+        };
+        let today = date(2024, 12, 2);
+        assert_eq!(
+            from_only.export_file_name("submissions", None, today, "csv"),
+            "submissions_from-2024-08-01_2024-12-02.csv"
+        );
+        assert_eq!(
+            to_only.export_file_name("submissions", None, today, "csv"),
+            "submissions_to-2024-11-30_2024-12-02.csv"
+        );
+    }
+
+    #[test]
+    fn export_file_name_strips_path_characters() {
+        let filter = SubmissionFilter {
+            semester: Some("../../etc".to_string()),
+            department: Some("Electronics / Communication".to_string()),
+            ..Default::default()
+        };
+        let name = filter.export_file_name("submissions", Some("A/B"), date(2024, 1, 5), "csv");
+        assert_eq!(
+            name,
+            "submissions_etc_a-b_electronics-communication_2024-01-05.csv"
+        );
+    }
+}
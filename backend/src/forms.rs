@@ -0,0 +1,365 @@
+use crate::error::{AppError, AppResult};
+use crate::models::RegistrationField;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+pub const FIELD_TYPES: [&str; 7] = [
+    "text",
+    "textarea",
+    "url",
+    "number",
+    "select",
+    "multiselect",
+    "checkbox",
+];
+
+const MAX_FIELDS: usize = 20;
+const MAX_TEXT_LENGTH: usize = 1000;
+const MAX_TEXTAREA_LENGTH: usize = 5000;
+
+// Checks the field definitions an admin attaches to a hackathon
+pub fn validate_field_definitions(fields: &[RegistrationField]) -> AppResult<()> {
+    if fields.len() > MAX_FIELDS {
+        return Err(AppError::BadRequest(format!(
+            "At most {} registration fields are allowed",
+            MAX_FIELDS
+        )));
+    }
+
+    let mut keys = HashSet::new();
+    for field in fields {
+        let key_is_valid = !field.key.is_empty()
+            && field.key.len() <= 50
+            && field
+                .key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !key_is_valid {
+            return Err(AppError::BadRequest(format!(
+                "Invalid field key '{}': use lowercase letters, digits and underscores",
+                field.key
+            )));
+        }
+
+        if !keys.insert(field.key.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Duplicate field key '{}'",
+                field.key
+            )));
+        }
+
+        if field.label.trim().is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Field '{}' needs a label",
+                field.key
+            )));
+        }
+
+        if !FIELD_TYPES.contains(&field.field_type.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Field '{}' has unsupported type '{}'",
+                field.key, field.field_type
+            )));
+        }
+
+        let has_options = matches!(field.field_type.as_str(), "select" | "multiselect");
+        if has_options && field.options.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Field '{}' needs at least one option",
+                field.key
+            )));
+        }
+        if !has_options && !field.options.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Field '{}' of type '{}' does not take options",
+                field.key, field.field_type
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// Validates submitted answers against a hackathon's fields and returns the
+// answers to store. Unknown keys are rejected so typos don't silently vanish.
+pub fn validate_answers(
+    fields: &[RegistrationField],
+    answers: &Map<String, Value>,
+) -> AppResult<Map<String, Value>> {
+    if let Some(unknown) = answers
+        .keys()
+        .find(|key| !fields.iter().any(|f| &f.key == *key))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Unknown registration field '{}'",
+            unknown
+        )));
+    }
+
+    let mut cleaned = Map::new();
+    for field in fields {
+        let value = answers.get(&field.key).filter(|v| !is_blank(v));
+
+        let Some(value) = value else {
+            if field.required {
+                return Err(AppError::BadRequest(format!(
+                    "'{}' is required",
+                    field.label
+                )));
+            }
+            continue;
+        };
+
+        cleaned.insert(field.key.clone(), validate_answer(field, value)?);
+    }
+
+    Ok(cleaned)
+}
+
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+fn validate_answer(field: &RegistrationField, value: &Value) -> AppResult<Value> {
+    let invalid = |reason: &str| AppError::BadRequest(format!("'{}' {}", field.label, reason));
+
+    match field.field_type.as_str() {
+        "text" | "textarea" | "url" => {
+            let text = value
+                .as_str()
+                .ok_or_else(|| invalid("must be text"))?
+                .trim();
+            let max_length = if field.field_type == "textarea" {
+                MAX_TEXTAREA_LENGTH
+            } else {
+                MAX_TEXT_LENGTH
+            };
+            if text.chars().count() > max_length {
+                return Err(invalid(&format!(
+                    "must be at most {} characters",
+                    max_length
+                )));
+            }
+            if field.field_type == "url"
+                && !(text.starts_with("https://") || text.starts_with("http://"))
+            {
+                return Err(invalid("must be an http(s) URL"));
+            }
+            Ok(Value::String(text.to_string()))
+        }
+        "number" => {
+            if value.is_number() {
+                Ok(value.clone())
+            } else {
+                Err(invalid("must be a number"))
+            }
+        }
+        "select" => {
+            let choice = value.as_str().ok_or_else(|| invalid("must be text"))?;
+            if field.options.iter().any(|o| o == choice) {
+                Ok(value.clone())
+            } else {
+                Err(invalid("must be one of the listed options"))
+            }
+        }
+        "multiselect" => {
+            let choices = value
+                .as_array()
+                .ok_or_else(|| invalid("must be a list of options"))?;
+            let all_valid = choices.iter().all(|choice| {
+                choice
+                    .as_str()
+                    .map(|c| field.options.iter().any(|o| o == c))
+                    .unwrap_or(false)
+            });
+            if !all_valid {
+                return Err(invalid("must only contain listed options"));
+            }
+            let mut seen = HashSet::new();
+            if !choices.iter().all(|choice| seen.insert(choice.as_str())) {
+                return Err(invalid("must not list an option twice"));
+            }
+            Ok(value.clone())
+        }
+        "checkbox" => match value.as_bool() {
+            Some(false) if field.required => Err(invalid("must be checked")),
+            Some(checked) => Ok(Value::Bool(checked)),
+            None => Err(invalid("must be true or false")),
+        },
+        _ => Err(invalid("has an unsupported type")),
+    }
+}
+
+// Flattens stored answers into a single export cell, e.g. `repo: https://...; track: AI`
+pub fn format_answers(answers: &Value) -> String {
+    let Some(map) = answers.as_object() else {
+        return String::new();
+    };
+
+    map.iter()
+        .map(|(key, value)| format!("{}: {}", key, format_answer_value(value)))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub fn format_answer_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(format_answer_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(key: &str, field_type: &str, required: bool, options: &[&str]) -> RegistrationField {
+        RegistrationField {
+            key: key.to_string(),
+            label: key.to_string(),
+            field_type: field_type.to_string(),
+            required,
+            options: options.iter().map(|o| o.to_string()).collect(),
+        }
+    }
+
+    fn answers(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    fn check(field: RegistrationField, value: Value) -> AppResult<Map<String, Value>> {
+        let key = field.key.clone();
+        validate_answers(&[field], &answers(json!({ key: value })))
+    }
+
+    #[test]
+    fn text_fields_are_trimmed_and_length_limited() {
+        let cleaned = check(field("team_bio", "text", false, &[]), json!("  hello  ")).unwrap();
+        assert_eq!(cleaned["team_bio"], json!("hello"));
+
+        assert!(check(field("team_bio", "text", false, &[]), json!(42)).is_err());
+        assert!(check(
+            field("team_bio", "text", false, &[]),
+            json!("a".repeat(1001))
+        )
+        .is_err());
+        assert!(check(
+            field("pitch", "textarea", false, &[]),
+            json!("a".repeat(5000))
+        )
+        .is_ok());
+        assert!(check(
+            field("pitch", "textarea", false, &[]),
+            json!("a".repeat(5001))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn url_fields_need_an_http_scheme() {
+        assert!(check(
+            field("repo", "url", false, &[]),
+            json!("https://git.example")
+        )
+        .is_ok());
+        assert!(check(
+            field("repo", "url", false, &[]),
+            json!("http://git.example")
+        )
+        .is_ok());
+        assert!(check(
+            field("repo", "url", false, &[]),
+            json!("javascript:alert(1)")
+        )
+        .is_err());
+        assert!(check(field("repo", "url", false, &[]), json!("git.example")).is_err());
+    }
+
+    #[test]
+    fn number_fields_take_numbers_only() {
+        assert!(check(field("size", "number", false, &[]), json!(4)).is_ok());
+        assert!(check(field("size", "number", false, &[]), json!(2.5)).is_ok());
+        assert!(check(field("size", "number", false, &[]), json!("4")).is_err());
+    }
+
+    #[test]
+    fn select_fields_take_one_listed_option() {
+        let track = || field("track", "select", false, &["AI", "Web"]);
+        assert!(check(track(), json!("AI")).is_ok());
+        assert!(check(track(), json!("ai")).is_err());
+        assert!(check(track(), json!(["AI"])).is_err());
+    }
+
+    #[test]
+    fn multiselect_fields_take_distinct_listed_options() {
+        let stack = || field("stack", "multiselect", false, &["Rust", "Go", "TS"]);
+        assert!(check(stack(), json!(["Rust", "TS"])).is_ok());
+        assert!(check(stack(), json!(["Rust", "Java"])).is_err());
+        assert!(check(stack(), json!(["Rust", 1])).is_err());
+        assert!(check(stack(), json!("Rust")).is_err());
+        assert!(check(stack(), json!(["Rust", "Rust"])).is_err());
+    }
+
+    #[test]
+    fn checkbox_fields_take_booleans() {
+        assert!(check(field("agree", "checkbox", false, &[]), json!(false)).is_ok());
+        assert!(check(field("agree", "checkbox", true, &[]), json!(true)).is_ok());
+        assert!(check(field("agree", "checkbox", true, &[]), json!(false)).is_err());
+        assert!(check(field("agree", "checkbox", false, &[]), json!("yes")).is_err());
+    }
+
+    #[test]
+    fn required_fields_must_be_answered() {
+        let fields = [field("repo", "url", true, &[])];
+        for blank in [json!({}), json!({ "repo": null }), json!({ "repo": "  " })] {
+            assert!(validate_answers(&fields, &answers(blank)).is_err());
+        }
+
+        let fields = [field("stack", "multiselect", true, &["Rust"])];
+        assert!(validate_answers(&fields, &answers(json!({ "stack": [] }))).is_err());
+    }
+
+    #[test]
+    fn optional_fields_can_be_left_out() {
+        let fields = [
+            field("repo", "url", false, &[]),
+            field("notes", "textarea", false, &[]),
+        ];
+        let cleaned =
+            validate_answers(&fields, &answers(json!({ "repo": "", "notes": null }))).unwrap();
+        assert!(cleaned.is_empty());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let fields = [field("repo", "url", false, &[])];
+        let result = validate_answers(&fields, &answers(json!({ "rep": "https://x.io" })));
+        assert!(matches!(result, Err(AppError::BadRequest(message)) if message.contains("rep")));
+    }
+
+    #[test]
+    fn field_definitions_are_checked() {
+        assert!(validate_field_definitions(&[field("repo", "url", true, &[])]).is_ok());
+        assert!(validate_field_definitions(&[field("Repo", "url", true, &[])]).is_err());
+        assert!(validate_field_definitions(&[field("repo", "date", true, &[])]).is_err());
+        assert!(validate_field_definitions(&[field("track", "select", true, &[])]).is_err());
+        assert!(validate_field_definitions(&[field("repo", "url", true, &["x"])]).is_err());
+        assert!(validate_field_definitions(&[
+            field("repo", "url", true, &[]),
+            field("repo", "text", false, &[]),
+        ])
+        .is_err());
+    }
+}
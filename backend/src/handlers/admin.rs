@@ -1,14 +1,22 @@
 use crate::auth::{create_jwt, verify_password};
+use crate::certificates;
+use crate::eligibility;
 use crate::error::{AppError, AppResult};
+use crate::events;
+use crate::filters::{sort_direction, submission_sort_column, SubmissionFilter};
+use crate::forms::validate_field_definitions;
 use crate::models::*;
+use crate::submissions;
 use crate::utils::generate_slug;
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::IntoResponse,
     Json,
 };
 use serde::Deserialize;
 use serde_json::json;
+use sqlx::{PgConnection, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 use crate::AppState;
@@ -59,10 +67,21 @@ pub async fn create_hackathon(
     // Use admin_id from JWT claims
     let created_by = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid admin ID in token".to_string()))?;
-
+    let registration_fields = req.registration_fields.unwrap_or_default();
+    validate_field_definitions(&registration_fields)?;
+
+    let min_team_size = req.min_team_size.unwrap_or(1);
+    let max_team_size = req.max_team_size.unwrap_or(10);
+    let max_mentors = req.max_mentors.unwrap_or(5);
+    let allowed_years = req.allowed_years.unwrap_or_default();
+    let allowed_branches = req.allowed_branches.unwrap_or_default();
+    eligibility::validate_rules(min_team_size, max_team_size, max_mentors, &allowed_years)?;
+    validate_team_capacity(req.team_capacity)?;
+
+    let mut tx = state.db.begin().await?;
     let hackathon = sqlx::query_as::<_, Hackathon>(
-        "INSERT INTO hackathons (id, name, slug, organizer, description, mode, location, start_date, end_date, registration_deadline, official_registration_link, eligibility, status, semester, created_by)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        "INSERT INTO hackathons (id, name, slug, organizer, description, mode, location, start_date, end_date, registration_deadline, official_registration_link, eligibility, status, semester, created_by, registration_fields, min_team_size, max_team_size, max_mentors, allowed_years, allowed_branches, team_capacity)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22)
          RETURNING *"
     )
     .bind(&id)
@@ -80,9 +99,19 @@ pub async fn create_hackathon(
     .bind(req.status.as_deref().unwrap_or("UPCOMING"))
     .bind(&req.semester)
     .bind(&created_by)
-    .fetch_one(&state.db)
+    .bind(sqlx::types::Json(registration_fields))
+    .bind(min_team_size)
+    .bind(max_team_size)
+    .bind(max_mentors)
+    .bind(allowed_years)
+    .bind(allowed_branches)
+    .bind(req.team_capacity)
+    .fetch_one(&mut *tx)
     .await?;
 
+    events::hackathon_created(&mut tx, &hackathon).await?;
+    tx.commit().await?;
+
     Ok((StatusCode::CREATED, Json(hackathon)))
 }
 
@@ -105,13 +134,32 @@ pub async fn update_hackathon(
     let hackathon_id = Uuid::parse_str(&id)
         .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))?;
 
+    if let Some(fields) = &req.registration_fields {
+        validate_field_definitions(fields)?;
+    }
+
     // Verify hackathon exists
-    let _: (String,) = sqlx::query_as("SELECT id::text FROM hackathons WHERE id = $1")
+    let existing: Hackathon = sqlx::query_as("SELECT * FROM hackathons WHERE id = $1")
         .bind(hackathon_id)
         .fetch_optional(&state.db)
         .await?
         .ok_or_else(|| AppError::NotFound("Hackathon not found".to_string()))?;
 
+    // Validate team rules as they will be after the update
+    eligibility::validate_rules(
+        req.min_team_size.unwrap_or(existing.min_team_size),
+        req.max_team_size.unwrap_or(existing.max_team_size),
+        req.max_mentors.unwrap_or(existing.max_mentors),
+        req.allowed_years
+            .as_deref()
+            .unwrap_or(&existing.allowed_years),
+    )?;
+    if let Some(capacity) = req.team_capacity {
+        validate_team_capacity(capacity)?;
+    }
+
+    let mut tx = state.db.begin().await?;
+
     let hackathon: Hackathon = sqlx::query_as(
         "UPDATE hackathons SET 
             name = COALESCE($2, name),
@@ -125,6 +173,13 @@ pub async fn update_hackathon(
             official_registration_link = COALESCE($10, official_registration_link),
             eligibility = COALESCE($11, eligibility),
             semester = COALESCE($12, semester),
+            registration_fields = COALESCE($13, registration_fields),
+            min_team_size = COALESCE($14, min_team_size),
+            max_team_size = COALESCE($15, max_team_size),
+            max_mentors = COALESCE($16, max_mentors),
+            allowed_years = COALESCE($17, allowed_years),
+            allowed_branches = COALESCE($18, allowed_branches),
+            team_capacity = CASE WHEN $19 THEN $20 ELSE team_capacity END,
             updated_at = NOW()
          WHERE id = $1
          RETURNING *",
@@ -141,12 +196,34 @@ pub async fn update_hackathon(
     .bind(&req.official_registration_link)
     .bind(&req.eligibility)
     .bind(&req.semester)
-    .fetch_one(&state.db)
+    .bind(req.registration_fields.as_ref().map(sqlx::types::Json))
+    .bind(req.min_team_size)
+    .bind(req.max_team_size)
+    .bind(req.max_mentors)
+    .bind(&req.allowed_years)
+    .bind(&req.allowed_branches)
+    .bind(req.team_capacity.is_some())
+    .bind(req.team_capacity.flatten())
+    .fetch_one(&mut *tx)
     .await?;
 
+    // A larger (or removed) capacity frees seats for waitlisted teams
+    submissions::promote_from_waitlist(&mut tx, hackathon_id, None).await?;
+    tx.commit().await?;
+
     Ok(Json(hackathon))
 }
 
+fn validate_team_capacity(capacity: Option<i32>) -> AppResult<()> {
+    if matches!(capacity, Some(capacity) if capacity < 1) {
+        return Err(AppError::BadRequest(
+            "Team capacity must be at least 1".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 pub async fn update_hackathon_status(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -188,48 +265,62 @@ pub async fn delete_hackathon(
 
 // Submission handlers
 #[derive(Deserialize)]
-pub struct SubmissionQuery {
-    pub hackathon_id: Option<String>,
-    pub status: Option<String>,
-    #[allow(dead_code)]
-    pub semester: Option<String>,
+pub struct SubmissionListQuery {
+    pub page: Option<i32>,
+    pub limit: Option<i32>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
 }
 
 pub async fn list_submissions(
     State(state): State<AppState>,
-    Query(query): Query<SubmissionQuery>,
-) -> AppResult<Json<Vec<Submission>>> {
-    let mut sql = "SELECT * FROM submissions WHERE 1=1".to_string();
-    let mut count = 1;
-
-    if query.hackathon_id.is_some() {
-        count += 1;
-        sql.push_str(&format!(" AND hackathon_id = ${}", count));
-    }
-
-    if query.status.is_some() {
-        count += 1;
-        sql.push_str(&format!(" AND status = ${}", count));
+    Query(filter): Query<SubmissionFilter>,
+    Query(query): Query<SubmissionListQuery>,
+) -> AppResult<Json<serde_json::Value>> {
+    let sort_column = submission_sort_column(query.sort.as_deref())?;
+    let direction = sort_direction(query.order.as_deref())?;
+
+    let mut qb = QueryBuilder::<Postgres>::new(
+        "SELECT s.* FROM submissions s JOIN hackathons h ON s.hackathon_id = h.id WHERE 1=1",
+    );
+    filter.push_conditions(&mut qb)?;
+    qb.push(format!(
+        " ORDER BY {} {}, s.id {}",
+        sort_column, direction, direction
+    ));
+
+    // Callers that ask for neither page nor limit get every matching
+    // submission as a plain array, as before pagination was added
+    if query.page.is_none() && query.limit.is_none() {
+        let submissions: Vec<Submission> = qb.build_query_as().fetch_all(&state.db).await?;
+        return Ok(Json(json!(submissions)));
     }
 
-    sql.push_str(" ORDER BY created_at DESC");
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let offset = (page - 1) * limit;
 
-    let mut q = sqlx::query_as::<_, Submission>(&sql);
+    qb.push(" LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let submissions: Vec<Submission> = qb.build_query_as().fetch_all(&state.db).await?;
 
-    if let Some(hid) = &query.hackathon_id {
-        q = q.bind(
-            Uuid::parse_str(hid)
-                .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))?,
-        );
-    }
-
-    if let Some(status) = &query.status {
-        q = q.bind(status);
-    }
-
-    let submissions = q.fetch_all(&state.db).await?;
+    let mut count_qb = QueryBuilder::<Postgres>::new(
+        "SELECT COUNT(*) FROM submissions s JOIN hackathons h ON s.hackathon_id = h.id WHERE 1=1",
+    );
+    filter.push_conditions(&mut count_qb)?;
+    let total: i64 = count_qb.build_query_scalar().fetch_one(&state.db).await?;
 
-    Ok(Json(submissions))
+    Ok(Json(json!({
+        "data": submissions,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": (total + limit as i64 - 1) / limit as i64
+        }
+    })))
 }
 
 pub async fn get_submission(
@@ -239,16 +330,15 @@ pub async fn get_submission(
     let submission_id = Uuid::parse_str(&id)
         .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
 
-    let submission: serde_json::Value = sqlx::query_as::<_, (serde_json::Value,)>(
-        "SELECT s.*, h.name as hackathon_name 
-         FROM submissions s 
-         JOIN hackathons h ON s.hackathon_id = h.id 
+    let submission: serde_json::Value = sqlx::query_scalar(
+        "SELECT to_jsonb(s) || jsonb_build_object('hackathon_name', h.name)
+         FROM submissions s
+         JOIN hackathons h ON s.hackathon_id = h.id
          WHERE s.id = $1",
     )
     .bind(submission_id)
     .fetch_optional(&state.db)
     .await?
-    .map(|(v,)| v)
     .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
 
     let participants: Vec<Participant> =
@@ -262,13 +352,83 @@ pub async fn get_submission(
         .fetch_all(&state.db)
         .await?;
 
+    let result: Option<SubmissionResult> =
+        sqlx::query_as("SELECT * FROM submission_results WHERE submission_id = $1")
+            .bind(submission_id)
+            .fetch_optional(&state.db)
+            .await?;
+
+    let documents: Vec<SubmissionDocument> = sqlx::query_as(
+        "SELECT * FROM submission_documents WHERE submission_id = $1 ORDER BY created_at",
+    )
+    .bind(submission_id)
+    .fetch_all(&state.db)
+    .await?;
+
     Ok(Json(json!({
         "submission": submission,
         "participants": participants,
-        "mentors": mentors
+        "mentors": mentors,
+        "result": result,
+        "documents": documents
     })))
 }
 
+pub async fn download_submission_document(
+    State(state): State<AppState>,
+    Path((id, document_id)): Path<(String, String)>,
+) -> AppResult<impl IntoResponse> {
+    let submission_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
+    let document_id = Uuid::parse_str(&document_id)
+        .map_err(|_| AppError::BadRequest("Invalid document ID".to_string()))?;
+
+    let document: SubmissionDocument =
+        sqlx::query_as("SELECT * FROM submission_documents WHERE id = $1 AND submission_id = $2")
+            .bind(document_id)
+            .bind(submission_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
+
+    let data = state.storage.get(&document.storage_key).await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, document.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", document.file_name),
+            ),
+        ],
+        data,
+    ))
+}
+
+async fn submission_storage_keys(
+    conn: &mut PgConnection,
+    submission_id: Uuid,
+) -> AppResult<Vec<String>> {
+    let keys: Vec<String> =
+        sqlx::query_scalar("SELECT storage_key FROM submission_documents WHERE submission_id = $1")
+            .bind(submission_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+    Ok(keys)
+}
+
+// Stored files are removed after the database change commits; failures only
+// leave orphaned files behind, so they are logged rather than returned.
+async fn remove_stored_files(state: &AppState, keys: Vec<String>) {
+    for key in keys {
+        if let Err(e) = state.storage.delete(&key).await {
+            tracing::warn!("Failed to remove stored file {}: {}", key, e);
+        }
+    }
+}
+
 pub async fn update_submission_status(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -277,17 +437,22 @@ pub async fn update_submission_status(
     let submission_id = Uuid::parse_str(&id)
         .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
 
-    let submission: Submission =
-        sqlx::query_as("UPDATE submissions SET status = $2 WHERE id = $1 RETURNING *")
-            .bind(submission_id)
-            .bind(&req.status)
-            .fetch_optional(&state.db)
-            .await?
-            .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+    let status = parse_submission_status(&req.status)?;
+
+    let mut tx = state.db.begin().await?;
+    let submission = submissions::set_status(&mut tx, submission_id, &status)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+    tx.commit().await?;
 
     Ok(Json(submission))
 }
 
+fn parse_submission_status(status: &str) -> AppResult<SubmissionStatus> {
+    SubmissionStatus::parse(status)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid submission status: {}", status)))
+}
+
 pub async fn delete_submission(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -295,18 +460,255 @@ pub async fn delete_submission(
     let submission_id = Uuid::parse_str(&id)
         .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
 
-    let result = sqlx::query("DELETE FROM submissions WHERE id = $1")
+    let mut tx = state.db.begin().await?;
+    let storage_keys = submission_storage_keys(&mut tx, submission_id).await?;
+
+    if !submissions::delete(&mut tx, submission_id).await? {
+        return Err(AppError::NotFound("Submission not found".to_string()));
+    }
+
+    tx.commit().await?;
+    remove_stored_files(&state, storage_keys).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Submission result handlers
+pub async fn set_submission_result(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(req): Json<SetSubmissionResultRequest>,
+) -> AppResult<Json<SubmissionResult>> {
+    let submission_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
+    let recorded_by = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid admin ID in token".to_string()))?;
+
+    if !RESULT_OUTCOMES.contains(&req.outcome.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid outcome: {}",
+            req.outcome
+        )));
+    }
+    if matches!(req.rank, Some(rank) if rank < 1) {
+        return Err(AppError::BadRequest("Rank must be at least 1".to_string()));
+    }
+    if matches!(req.prize_amount, Some(amount) if amount < 0) {
+        return Err(AppError::BadRequest(
+            "Prize amount cannot be negative".to_string(),
+        ));
+    }
+
+    let _: (Uuid,) = sqlx::query_as("SELECT id FROM submissions WHERE id = $1")
+        .bind(submission_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+
+    let mut tx = state.db.begin().await?;
+    let result: SubmissionResult = sqlx::query_as(
+        "INSERT INTO submission_results (submission_id, outcome, rank, award_title, prize_amount, certificate_ref, recorded_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         ON CONFLICT (submission_id) DO UPDATE SET
+            outcome = EXCLUDED.outcome,
+            rank = EXCLUDED.rank,
+            award_title = EXCLUDED.award_title,
+            prize_amount = EXCLUDED.prize_amount,
+            certificate_ref = EXCLUDED.certificate_ref,
+            recorded_by = EXCLUDED.recorded_by,
+            updated_at = NOW()
+         RETURNING *",
+    )
+    .bind(submission_id)
+    .bind(&req.outcome)
+    .bind(req.rank)
+    .bind(&req.award_title)
+    .bind(req.prize_amount)
+    .bind(&req.certificate_ref)
+    .bind(recorded_by)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    certificates::reissue(&mut tx, submission_id).await?;
+    tx.commit().await?;
+
+    Ok(Json(result))
+}
+
+pub async fn delete_submission_result(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<StatusCode> {
+    let submission_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
+
+    let mut tx = state.db.begin().await?;
+    let result = sqlx::query("DELETE FROM submission_results WHERE submission_id = $1")
         .bind(submission_id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await?;
 
     if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Submission not found".to_string()));
+        return Err(AppError::NotFound(
+            "Submission result not found".to_string(),
+        ));
     }
 
+    certificates::reissue(&mut tx, submission_id).await?;
+    tx.commit().await?;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
+// Bulk submission handlers
+const MAX_BULK_ITEMS: usize = 1000;
+
+// Resolves a bulk selection to submission IDs. IDs that fail to parse are
+// reported as failed items rather than aborting the whole operation.
+async fn resolve_bulk_selection(
+    conn: &mut PgConnection,
+    selection: &BulkSelection,
+) -> AppResult<(Vec<Uuid>, Vec<BulkItemResult>)> {
+    match (&selection.ids, &selection.filter) {
+        (Some(ids), None) => {
+            if ids.is_empty() {
+                return Err(AppError::BadRequest(
+                    "No submission IDs provided".to_string(),
+                ));
+            }
+            if ids.len() > MAX_BULK_ITEMS {
+                return Err(AppError::BadRequest(format!(
+                    "At most {} submissions can be processed at once",
+                    MAX_BULK_ITEMS
+                )));
+            }
+
+            let mut parsed = Vec::with_capacity(ids.len());
+            let mut invalid = Vec::new();
+            for id in ids {
+                match Uuid::parse_str(id) {
+                    Ok(uuid) if !parsed.contains(&uuid) => parsed.push(uuid),
+                    Ok(_) => {}
+                    Err(_) => invalid.push(BulkItemResult {
+                        id: id.clone(),
+                        success: false,
+                        error: Some("Invalid submission ID".to_string()),
+                    }),
+                }
+            }
+            Ok((parsed, invalid))
+        }
+        (None, Some(filter)) => {
+            // An empty filter would match every submission; require at least one criterion.
+            if filter.is_empty() {
+                return Err(AppError::BadRequest(
+                    "Filter must specify at least one criterion".to_string(),
+                ));
+            }
+
+            let mut qb = QueryBuilder::<Postgres>::new(
+                "SELECT s.id FROM submissions s JOIN hackathons h ON s.hackathon_id = h.id WHERE 1=1",
+            );
+            filter.push_conditions(&mut qb)?;
+            // Rows are locked afterwards by `submissions::lock_submissions`
+            qb.push(" ORDER BY s.created_at LIMIT ")
+                .push_bind(MAX_BULK_ITEMS as i64 + 1);
+
+            let ids: Vec<Uuid> = qb.build_query_scalar().fetch_all(&mut *conn).await?;
+            if ids.len() > MAX_BULK_ITEMS {
+                return Err(AppError::BadRequest(format!(
+                    "Filter matches more than {} submissions",
+                    MAX_BULK_ITEMS
+                )));
+            }
+            Ok((ids, Vec::new()))
+        }
+        _ => Err(AppError::BadRequest(
+            "Provide either ids or filter".to_string(),
+        )),
+    }
+}
+
+fn bulk_response(results: Vec<BulkItemResult>) -> BulkOperationResponse {
+    let succeeded = results.iter().filter(|r| r.success).count();
+    BulkOperationResponse {
+        processed: results.len(),
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    }
+}
+
+async fn bulk_set_status(
+    state: &AppState,
+    selection: &BulkSelection,
+    status: &SubmissionStatus,
+) -> AppResult<BulkOperationResponse> {
+    let mut tx = state.db.begin().await?;
+    let (ids, mut results) = resolve_bulk_selection(&mut tx, selection).await?;
+    submissions::lock_submissions(&mut tx, &ids).await?;
+
+    for id in ids {
+        let updated = submissions::set_status(&mut tx, id, status).await?;
+        results.push(BulkItemResult {
+            id: id.to_string(),
+            success: updated.is_some(),
+            error: updated
+                .is_none()
+                .then(|| "Submission not found".to_string()),
+        });
+    }
+
+    tx.commit().await?;
+
+    Ok(bulk_response(results))
+}
+
+pub async fn bulk_update_submission_status(
+    State(state): State<AppState>,
+    Json(req): Json<BulkStatusRequest>,
+) -> AppResult<Json<BulkOperationResponse>> {
+    let status = parse_submission_status(&req.status)?;
+    let response = bulk_set_status(&state, &req.selection, &status).await?;
+
+    Ok(Json(response))
+}
+
+pub async fn bulk_archive_submissions(
+    State(state): State<AppState>,
+    Json(req): Json<BulkSelection>,
+) -> AppResult<Json<BulkOperationResponse>> {
+    let response = bulk_set_status(&state, &req, &SubmissionStatus::Archived).await?;
+
+    Ok(Json(response))
+}
+
+pub async fn bulk_delete_submissions(
+    State(state): State<AppState>,
+    Json(req): Json<BulkSelection>,
+) -> AppResult<Json<BulkOperationResponse>> {
+    let mut tx = state.db.begin().await?;
+    let (ids, mut results) = resolve_bulk_selection(&mut tx, &req).await?;
+    submissions::lock_submissions(&mut tx, &ids).await?;
+    let mut storage_keys = Vec::new();
+
+    for id in ids {
+        storage_keys.extend(submission_storage_keys(&mut tx, id).await?);
+        let deleted = submissions::delete(&mut tx, id).await?;
+        results.push(BulkItemResult {
+            id: id.to_string(),
+            success: deleted,
+            error: (!deleted).then(|| "Submission not found".to_string()),
+        });
+    }
+
+    tx.commit().await?;
+    remove_stored_files(&state, storage_keys).await;
+
+    Ok(Json(bulk_response(results)))
+}
+
 // Blog handlers
 pub async fn create_blog_post(
     State(state): State<AppState>,
@@ -316,6 +718,7 @@ pub async fn create_blog_post(
     let slug = generate_slug(&req.title);
     let related_hackathon = req.related_hackathon.and_then(|h| Uuid::parse_str(&h).ok());
 
+    let mut tx = state.db.begin().await?;
     let post: BlogPost = sqlx::query_as(
         "INSERT INTO blog_posts (id, title, slug, summary, content, category, author, related_hackathon, status)
          VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
@@ -330,9 +733,14 @@ pub async fn create_blog_post(
     .bind(&req.author)
     .bind(&related_hackathon)
     .bind(req.status.as_deref().unwrap_or("draft"))
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
+    if post.status == "published" {
+        events::blog_post_published(&mut tx, &post).await?;
+    }
+    tx.commit().await?;
+
     Ok((StatusCode::CREATED, Json(post)))
 }
 
@@ -347,6 +755,14 @@ pub async fn update_blog_post(
     // Generate new slug if title changed
     let slug = req.title.as_ref().map(|t| generate_slug(t));
 
+    let mut tx = state.db.begin().await?;
+    let previous_status: String =
+        sqlx::query_scalar("SELECT status FROM blog_posts WHERE id = $1 FOR UPDATE")
+            .bind(post_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Blog post not found".to_string()))?;
+
     let post: BlogPost = sqlx::query_as(
         "UPDATE blog_posts SET
             title = COALESCE($2, title),
@@ -368,9 +784,14 @@ pub async fn update_blog_post(
     .bind(&req.category)
     .bind(&req.author)
     .bind(&req.status)
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or_else(|| AppError::NotFound("Blog post not found".to_string()))?;
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Students hear about a post the first time it goes live
+    if post.status == "published" && previous_status != "published" {
+        events::blog_post_published(&mut tx, &post).await?;
+    }
+    tx.commit().await?;
 
     Ok(Json(post))
 }
@@ -0,0 +1,312 @@
+use crate::certificates;
+use crate::error::{AppError, AppResult};
+use crate::models::{Certificate, CertificateTemplate, CertificateVerification};
+use crate::utils::{detect_document_type, sanitize_file_name};
+use crate::AppState;
+use axum::{
+    extract::{multipart::MultipartError, Multipart, Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+pub const MAX_BACKGROUND_SIZE: usize = 5 * 1024 * 1024;
+pub const TEMPLATE_UPLOAD_BODY_LIMIT: usize = MAX_BACKGROUND_SIZE + 64 * 1024;
+
+const DEFAULT_TITLE: &str = "Certificate of Participation";
+const DEFAULT_BODY: &str = "This is to certify that {name} of team {team} participated in {hackathon} and is awarded: {award}.";
+
+fn parse_hackathon_id(id: &str) -> AppResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))
+}
+
+fn multipart_error(e: MultipartError) -> AppError {
+    AppError::BadRequest(format!("Invalid multipart body: {}", e))
+}
+
+fn file_response(file_name: String, data: Vec<u8>, content_type: &str) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        data,
+    )
+}
+
+pub async fn get_certificate_template(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<CertificateTemplate>> {
+    let template: CertificateTemplate =
+        sqlx::query_as("SELECT * FROM certificate_templates WHERE hackathon_id = $1")
+            .bind(parse_hackathon_id(&id)?)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Certificate template not found".to_string()))?;
+
+    Ok(Json(template))
+}
+
+// Multipart form with optional `title`, `body`, `signatory_name`,
+// `signatory_title` and a PNG/JPEG `background`. Fields left out keep their
+// current value. `{name}`, `{team}`, `{hackathon}`, `{award}` and `{date}` in
+// the title and body are filled in per certificate.
+pub async fn upload_certificate_template(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> AppResult<Json<CertificateTemplate>> {
+    let hackathon_id = parse_hackathon_id(&id)?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM hackathons WHERE id = $1)")
+        .bind(hackathon_id)
+        .fetch_one(&state.db)
+        .await?;
+    if !exists {
+        return Err(AppError::NotFound("Hackathon not found".to_string()));
+    }
+
+    let mut title = None;
+    let mut body = None;
+    let mut signatory_name = None;
+    let mut signatory_title = None;
+    let mut background = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "title" => title = Some(field.text().await.map_err(multipart_error)?),
+            "body" => body = Some(field.text().await.map_err(multipart_error)?),
+            "signatory_name" => signatory_name = Some(field.text().await.map_err(multipart_error)?),
+            "signatory_title" => {
+                signatory_title = Some(field.text().await.map_err(multipart_error)?)
+            }
+            "background" => background = Some(field.bytes().await.map_err(multipart_error)?),
+            _ => {}
+        }
+    }
+
+    if title.as_deref().is_some_and(|t| t.trim().is_empty())
+        || body.as_deref().is_some_and(|b| b.trim().is_empty())
+    {
+        return Err(AppError::BadRequest(
+            "Title and body can't be empty".to_string(),
+        ));
+    }
+
+    let mut background_key = None;
+    if let Some(data) = background {
+        if data.len() > MAX_BACKGROUND_SIZE {
+            return Err(AppError::BadRequest(format!(
+                "Background exceeds the {} MB limit",
+                MAX_BACKGROUND_SIZE / (1024 * 1024)
+            )));
+        }
+        let extension = match detect_document_type(&data) {
+            Some("image/png") => "png",
+            Some("image/jpeg") => "jpg",
+            _ => {
+                return Err(AppError::BadRequest(
+                    "Background must be a PNG or JPEG image".to_string(),
+                ))
+            }
+        };
+        // Fail on images printpdf can't read now rather than at issue time
+        let image = data.clone();
+        tokio::task::spawn_blocking(move || crate::pdf::decode_background(&image))
+            .await
+            .map_err(|e| AppError::InternalError(format!("Background decoding failed: {}", e)))??;
+
+        let key = format!(
+            "certificate-templates/{}/{}.{}",
+            hackathon_id,
+            Uuid::new_v4(),
+            extension
+        );
+        state.storage.put(&key, &data).await?;
+        background_key = Some(key);
+    }
+
+    let previous_key: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT background_key FROM certificate_templates WHERE hackathon_id = $1",
+    )
+    .bind(hackathon_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let template: CertificateTemplate = sqlx::query_as(
+        "INSERT INTO certificate_templates
+            (hackathon_id, title, body, signatory_name, signatory_title, background_key)
+         VALUES ($1, COALESCE($2, $7), COALESCE($3, $8), $4, $5, $6)
+         ON CONFLICT (hackathon_id) DO UPDATE SET
+            title = COALESCE($2, certificate_templates.title),
+            body = COALESCE($3, certificate_templates.body),
+            signatory_name = COALESCE($4, certificate_templates.signatory_name),
+            signatory_title = COALESCE($5, certificate_templates.signatory_title),
+            background_key = COALESCE($6, certificate_templates.background_key),
+            updated_at = NOW()
+         RETURNING *",
+    )
+    .bind(hackathon_id)
+    .bind(title.map(|t| t.trim().to_string()))
+    .bind(body.map(|b| b.trim().to_string()))
+    .bind(signatory_name.map(|s| s.trim().to_string()))
+    .bind(signatory_title.map(|s| s.trim().to_string()))
+    .bind(&background_key)
+    .bind(DEFAULT_TITLE)
+    .bind(DEFAULT_BODY)
+    .fetch_one(&state.db)
+    .await?;
+
+    // The old background is no longer referenced once a new one is saved
+    if let (Some(_), Some(Some(old_key))) = (&background_key, previous_key) {
+        state.certificate_backgrounds.remove(&old_key);
+        if let Err(e) = state.storage.delete(&old_key).await {
+            tracing::warn!(
+                "Failed to remove old certificate background {}: {}",
+                old_key,
+                e
+            );
+        }
+    }
+
+    Ok(Json(template))
+}
+
+pub async fn issue_certificates(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    let hackathon_id = parse_hackathon_id(&id)?;
+
+    let mut tx = state.db.begin().await?;
+    // Serialise issuing per hackathon so concurrent requests don't issue twice
+    sqlx::query("SELECT id FROM hackathons WHERE id = $1 FOR UPDATE")
+        .bind(hackathon_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Hackathon not found".to_string()))?;
+
+    let issued = certificates::issue(&mut tx, hackathon_id).await?;
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM certificates WHERE hackathon_id = $1")
+            .bind(hackathon_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(json!({
+        "issued": issued,
+        "total": total
+    })))
+}
+
+pub async fn list_certificates(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Vec<Certificate>>> {
+    let certificates: Vec<Certificate> = sqlx::query_as(
+        "SELECT * FROM certificates WHERE hackathon_id = $1 ORDER BY team_name, participant_name",
+    )
+    .bind(parse_hackathon_id(&id)?)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(certificates))
+}
+
+// ZIP of every certificate issued for the hackathon
+pub async fn download_certificates(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    let hackathon_id = parse_hackathon_id(&id)?;
+
+    let issued: Vec<Certificate> = sqlx::query_as(
+        "SELECT * FROM certificates
+         WHERE hackathon_id = $1 AND revoked_at IS NULL
+         ORDER BY team_name, participant_name",
+    )
+    .bind(hackathon_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    if issued.is_empty() {
+        return Err(AppError::BadRequest(
+            "No certificates have been issued for this hackathon".to_string(),
+        ));
+    }
+
+    let (template, background) = certificates::load_template(
+        &state.db,
+        state.storage.as_ref(),
+        &state.certificate_backgrounds,
+        hackathon_id,
+    )
+    .await?;
+    let file_name = sanitize_file_name(
+        &format!("certificates-{}.zip", issued[0].hackathon_name).replace(['/', '\\'], "_"),
+    );
+
+    // Rendering hundreds of PDFs is CPU work; keep it off the async workers
+    let public_api_url = state.public_api_url.clone();
+    let data = tokio::task::spawn_blocking(move || {
+        certificates::zip_certificates(&template, background.as_deref(), &issued, &public_api_url)
+    })
+    .await
+    .map_err(|e| AppError::InternalError(format!("Certificate rendering failed: {}", e)))??;
+
+    Ok(file_response(file_name, data, "application/zip"))
+}
+
+// Public check that a certificate is genuine
+pub async fn verify_certificate(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> AppResult<Json<CertificateVerification>> {
+    Ok(Json(certificates::verify(&state.db, &code).await?))
+}
+
+// Anyone holding a verification code can download that certificate
+pub async fn download_certificate(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    let certificate = certificates::find_by_code(&state.db, &code).await?;
+    if certificate.revoked_at.is_some() {
+        return Err(AppError::BadRequest(
+            "This certificate has been revoked".to_string(),
+        ));
+    }
+
+    let (template, background) = certificates::load_template(
+        &state.db,
+        state.storage.as_ref(),
+        &state.certificate_backgrounds,
+        certificate.hackathon_id,
+    )
+    .await?;
+
+    let file_name = certificates::file_name(&certificate);
+    let public_api_url = state.public_api_url.clone();
+    let data = tokio::task::spawn_blocking(move || {
+        certificates::render(
+            &template,
+            background.as_deref(),
+            &certificate,
+            &public_api_url,
+        )
+    })
+    .await
+    .map_err(|e| AppError::InternalError(format!("Certificate rendering failed: {}", e)))??;
+
+    Ok(file_response(file_name, data, "application/pdf"))
+}
@@ -0,0 +1,83 @@
+use crate::auth::{create_stream_ticket, verify_stream_ticket};
+use crate::error::{AppError, AppResult};
+use crate::middleware::bearer_claims;
+use crate::models::Claims;
+use crate::AppState;
+use axum::{
+    extract::{Extension, Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use chrono::Utc;
+use futures::stream::{self, Stream};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{timeout_at, Instant};
+
+#[derive(Deserialize)]
+pub struct EventStreamQuery {
+    pub ticket: Option<String>,
+}
+
+// Browsers' EventSource can't send an Authorization header, so the dashboard
+// fetches a ticket with its session and opens `/admin/events?ticket=...`
+pub async fn create_event_ticket(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<serde_json::Value>> {
+    let ticket = create_stream_ticket(&claims, &state.jwt_secret)?;
+    Ok(Json(json!({ "ticket": ticket })))
+}
+
+// Server-sent events for the admin dashboard: `submission.created` and
+// `submission.status_changed`. A `resync` event means some events were missed
+// and the client should refetch. The stream ends with `session_expired` once
+// the admin's session runs out.
+pub async fn admin_event_stream(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<EventStreamQuery>,
+) -> AppResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let session_exp = match &query.ticket {
+        Some(ticket) => verify_stream_ticket(ticket, &state.jwt_secret)?.session_exp,
+        None => {
+            let claims = bearer_claims(&headers, &state.jwt_secret)?;
+            if claims.role != "admin" {
+                return Err(AppError::Forbidden("Admin access required".to_string()));
+            }
+            claims.exp
+        }
+    };
+
+    let remaining = (session_exp - Utc::now().timestamp()).max(0) as u64;
+    let deadline = Instant::now() + Duration::from_secs(remaining);
+    let receiver = state.admin_events.subscribe();
+
+    let events = stream::unfold(Some(receiver), move |receiver| async move {
+        let mut receiver = receiver?;
+        let event = match timeout_at(deadline, receiver.recv()).await {
+            Ok(Ok(admin_event)) => Event::default()
+                .event(admin_event.event)
+                .data(admin_event.data),
+            Ok(Err(RecvError::Lagged(skipped))) => Event::default()
+                .event("resync")
+                .data(format!("{{\"skipped\":{}}}", skipped)),
+            Ok(Err(RecvError::Closed)) => return None,
+            // Last event before closing; reconnecting needs a fresh login
+            Err(_) => {
+                return Some((
+                    Ok(Event::default().event("session_expired").data("{}")),
+                    None,
+                ))
+            }
+        };
+
+        Some((Ok(event), Some(receiver)))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
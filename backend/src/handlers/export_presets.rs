@@ -0,0 +1,158 @@
+use crate::error::{AppError, AppResult};
+use crate::export::{validate_columns, validate_format, validate_layout};
+use crate::models::{Claims, CreateExportPresetRequest, ExportPreset, UpdateExportPresetRequest};
+use crate::AppState;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn admin_id(claims: &Claims) -> AppResult<Uuid> {
+    Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid admin ID in token".to_string()))
+}
+
+fn parse_preset_id(id: &str) -> AppResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| AppError::BadRequest("Invalid preset ID".to_string()))
+}
+
+fn validate_name(name: &str) -> AppResult<()> {
+    if name.trim().is_empty() || name.trim().len() > 100 {
+        return Err(AppError::BadRequest(
+            "Preset name must be 1-100 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// Names are unique per admin. Relying on the constraint rather than checking
+// first means two concurrent saves can't both pass.
+fn name_conflict(name: &str) -> impl FnOnce(sqlx::Error) -> AppError + '_ {
+    move |e| match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+            AppError::Conflict(format!("You already have a preset named {}", name))
+        }
+        _ => e.into(),
+    }
+}
+
+// Admins only ever see their own presets
+pub async fn find_preset(db: &PgPool, claims: &Claims, id: &str) -> AppResult<ExportPreset> {
+    sqlx::query_as("SELECT * FROM export_presets WHERE id = $1 AND admin_id = $2")
+        .bind(parse_preset_id(id)?)
+        .bind(admin_id(claims)?)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Export preset not found".to_string()))
+}
+
+pub async fn list_presets(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<Vec<ExportPreset>>> {
+    let presets: Vec<ExportPreset> =
+        sqlx::query_as("SELECT * FROM export_presets WHERE admin_id = $1 ORDER BY name")
+            .bind(admin_id(&claims)?)
+            .fetch_all(&state.db)
+            .await?;
+
+    Ok(Json(presets))
+}
+
+pub async fn create_preset(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<CreateExportPresetRequest>,
+) -> AppResult<(StatusCode, Json<ExportPreset>)> {
+    let admin_id = admin_id(&claims)?;
+    let layout = req.layout.as_deref().unwrap_or("participant");
+    let format = req.format.as_deref().unwrap_or("csv");
+
+    validate_name(&req.name)?;
+    validate_columns(&req.columns)?;
+    validate_layout(layout)?;
+    validate_format(format)?;
+
+    let name = req.name.trim();
+    let preset: ExportPreset = sqlx::query_as(
+        "INSERT INTO export_presets (admin_id, name, columns, layout, format)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING *",
+    )
+    .bind(admin_id)
+    .bind(name)
+    .bind(&req.columns)
+    .bind(layout)
+    .bind(format)
+    .fetch_one(&state.db)
+    .await
+    .map_err(name_conflict(name))?;
+
+    Ok((StatusCode::CREATED, Json(preset)))
+}
+
+pub async fn update_preset(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateExportPresetRequest>,
+) -> AppResult<Json<ExportPreset>> {
+    let preset = find_preset(&state.db, &claims, &id).await?;
+
+    if let Some(name) = &req.name {
+        validate_name(name)?;
+    }
+    if let Some(columns) = &req.columns {
+        validate_columns(columns)?;
+    }
+    if let Some(layout) = &req.layout {
+        validate_layout(layout)?;
+    }
+    if let Some(format) = &req.format {
+        validate_format(format)?;
+    }
+
+    let preset: ExportPreset = sqlx::query_as(
+        "UPDATE export_presets SET
+            name = COALESCE($2, name),
+            columns = COALESCE($3, columns),
+            layout = COALESCE($4, layout),
+            format = COALESCE($5, format),
+            updated_at = NOW()
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(preset.id)
+    .bind(req.name.as_deref().map(str::trim))
+    .bind(&req.columns)
+    .bind(&req.layout)
+    .bind(&req.format)
+    .fetch_one(&state.db)
+    .await
+    .map_err(name_conflict(
+        req.name.as_deref().unwrap_or_default().trim(),
+    ))?;
+
+    Ok(Json(preset))
+}
+
+pub async fn delete_preset(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+) -> AppResult<StatusCode> {
+    let result = sqlx::query("DELETE FROM export_presets WHERE id = $1 AND admin_id = $2")
+        .bind(parse_preset_id(&id)?)
+        .bind(admin_id(&claims)?)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Export preset not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,257 @@
+use crate::auth::{create_jwt, hash_password, verify_password};
+use crate::error::{AppError, AppResult};
+use crate::events;
+use crate::models::{
+    Claims, CreateFacultyRequest, Faculty, FacultyLoginResponse, LoginRequest, Mentor, Mentorship,
+    Participant, RespondToMentorshipRequest, UpdateFacultyRequest, MENTORSHIP_STATUSES,
+};
+use crate::utils::{validate_password, validate_srec_email};
+use crate::AppState;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct MentorshipListQuery {
+    pub status: Option<String>,
+    pub semester: Option<String>,
+}
+
+fn faculty_id(claims: &Claims) -> AppResult<Uuid> {
+    Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid faculty ID in token".to_string()))
+}
+
+pub async fn login(
+    State(state): State<AppState>,
+    Json(req): Json<LoginRequest>,
+) -> AppResult<Json<FacultyLoginResponse>> {
+    let faculty: Faculty = sqlx::query_as("SELECT * FROM faculty WHERE email = $1 AND active")
+        .bind(&req.email)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Invalid credentials".to_string()))?;
+
+    if !verify_password(&req.password, &faculty.password_hash)? {
+        return Err(AppError::Unauthorized("Invalid credentials".to_string()));
+    }
+
+    let token = create_jwt(
+        &faculty.id.to_string(),
+        &faculty.email,
+        "faculty",
+        &state.jwt_secret,
+    )?;
+
+    Ok(Json(FacultyLoginResponse { token, faculty }))
+}
+
+pub async fn get_me(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<Faculty>> {
+    let faculty: Faculty = sqlx::query_as("SELECT * FROM faculty WHERE id = $1")
+        .bind(faculty_id(&claims)?)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Faculty member not found".to_string()))?;
+
+    Ok(Json(faculty))
+}
+
+// Teams that asked the signed-in faculty member to mentor them
+pub async fn list_mentorships(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<MentorshipListQuery>,
+) -> AppResult<Json<Vec<Mentorship>>> {
+    let mut qb = QueryBuilder::<Postgres>::new(
+        "SELECT m.id AS mentor_id, m.status, m.response_note, m.responded_at,
+                s.id AS submission_id, s.team_name, s.status AS submission_status,
+                s.created_at AS requested_at, h.id AS hackathon_id, h.name AS hackathon_name,
+                h.semester
+         FROM mentors m
+         JOIN submissions s ON m.submission_id = s.id
+         JOIN hackathons h ON s.hackathon_id = h.id
+         WHERE m.faculty_id = ",
+    );
+    qb.push_bind(faculty_id(&claims)?);
+
+    if let Some(status) = &query.status {
+        if !MENTORSHIP_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid status. Use one of: {}",
+                MENTORSHIP_STATUSES.join(", ")
+            )));
+        }
+        qb.push(" AND m.status = ").push_bind(status.clone());
+    }
+    if let Some(semester) = &query.semester {
+        qb.push(" AND h.semester = ").push_bind(semester.clone());
+    }
+    qb.push(" ORDER BY m.status = 'pending' DESC, s.created_at DESC");
+
+    let mut mentorships: Vec<Mentorship> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    let submission_ids: Vec<Uuid> = mentorships.iter().map(|m| m.submission_id).collect();
+    let participants: Vec<Participant> =
+        sqlx::query_as("SELECT * FROM participants WHERE submission_id = ANY($1) ORDER BY name")
+            .bind(&submission_ids)
+            .fetch_all(&state.db)
+            .await?;
+
+    for mentorship in &mut mentorships {
+        mentorship.participants = participants
+            .iter()
+            .filter(|p| p.submission_id == mentorship.submission_id)
+            .cloned()
+            .collect();
+    }
+
+    Ok(Json(mentorships))
+}
+
+// Approve or decline a pending mentorship request
+pub async fn respond_to_mentorship(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(req): Json<RespondToMentorshipRequest>,
+) -> AppResult<Json<Mentor>> {
+    let mentor_id =
+        Uuid::parse_str(&id).map_err(|_| AppError::BadRequest("Invalid mentor ID".to_string()))?;
+
+    if !matches!(req.status.as_str(), "approved" | "declined") {
+        return Err(AppError::BadRequest(
+            "Status must be approved or declined".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let current: Mentor =
+        sqlx::query_as("SELECT * FROM mentors WHERE id = $1 AND faculty_id = $2 FOR UPDATE")
+            .bind(mentor_id)
+            .bind(faculty_id(&claims)?)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Mentorship request not found".to_string()))?;
+
+    if current.status != "pending" {
+        return Err(AppError::Conflict(format!(
+            "Mentorship request was already {}",
+            current.status
+        )));
+    }
+
+    let note = req.note.filter(|note| !note.trim().is_empty());
+    let mentor: Mentor = sqlx::query_as(
+        "UPDATE mentors SET status = $2, response_note = $3, responded_at = NOW()
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(mentor_id)
+    .bind(&req.status)
+    .bind(&note)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    events::mentorship_responded(&mut tx, &mentor).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(mentor))
+}
+
+// Faculty accounts are created by admins rather than self-registered, so only
+// real faculty members show up in the mentor directory
+pub async fn create_faculty(
+    State(state): State<AppState>,
+    Json(req): Json<CreateFacultyRequest>,
+) -> AppResult<(StatusCode, Json<Faculty>)> {
+    validate_srec_email(&req.email)?;
+
+    if req.name.trim().is_empty() || req.department.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Name and department are required".to_string(),
+        ));
+    }
+
+    validate_password(&req.password)?;
+
+    let existing: Option<(i32,)> = sqlx::query_as("SELECT 1 FROM faculty WHERE email = $1")
+        .bind(&req.email)
+        .fetch_optional(&state.db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(AppError::BadRequest("Email already registered".to_string()));
+    }
+
+    let password_hash = hash_password(&req.password)?;
+
+    let faculty: Faculty = sqlx::query_as(
+        "INSERT INTO faculty (name, email, password_hash, department, designation)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING *",
+    )
+    .bind(req.name.trim())
+    .bind(&req.email)
+    .bind(&password_hash)
+    .bind(req.department.trim())
+    .bind(&req.designation)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(faculty)))
+}
+
+pub async fn list_faculty_admin(State(state): State<AppState>) -> AppResult<Json<Vec<Faculty>>> {
+    let faculty: Vec<Faculty> = sqlx::query_as("SELECT * FROM faculty ORDER BY department, name")
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(faculty))
+}
+
+pub async fn update_faculty(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateFacultyRequest>,
+) -> AppResult<Json<Faculty>> {
+    let faculty_id =
+        Uuid::parse_str(&id).map_err(|_| AppError::BadRequest("Invalid faculty ID".to_string()))?;
+
+    if let Some(password) = &req.password {
+        validate_password(password)?;
+    }
+    let password_hash = req.password.as_deref().map(hash_password).transpose()?;
+
+    let faculty: Faculty = sqlx::query_as(
+        "UPDATE faculty SET
+            name = COALESCE($2, name),
+            password_hash = COALESCE($3, password_hash),
+            department = COALESCE($4, department),
+            designation = COALESCE($5, designation),
+            active = COALESCE($6, active),
+            updated_at = NOW()
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(faculty_id)
+    .bind(&req.name)
+    .bind(&password_hash)
+    .bind(&req.department)
+    .bind(&req.designation)
+    .bind(req.active)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Faculty member not found".to_string()))?;
+
+    Ok(Json(faculty))
+}
@@ -0,0 +1,103 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{Job, JOB_STATUSES};
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct JobListQuery {
+    pub status: Option<String>,
+    pub kind: Option<String>,
+    pub page: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+pub async fn list_jobs(
+    State(state): State<AppState>,
+    Query(query): Query<JobListQuery>,
+) -> AppResult<Json<serde_json::Value>> {
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    if let Some(status) = &query.status {
+        if !JOB_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid job status: {}",
+                status
+            )));
+        }
+    }
+
+    let push_conditions = |qb: &mut QueryBuilder<'_, Postgres>| {
+        if let Some(status) = &query.status {
+            qb.push(" AND status = ").push_bind(status.clone());
+        }
+        if let Some(kind) = &query.kind {
+            qb.push(" AND kind = ").push_bind(kind.clone());
+        }
+    };
+
+    let mut count_qb = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM jobs WHERE 1=1");
+    push_conditions(&mut count_qb);
+    let total: i64 = count_qb.build_query_scalar().fetch_one(&state.db).await?;
+
+    let mut qb = QueryBuilder::<Postgres>::new("SELECT * FROM jobs WHERE 1=1");
+    push_conditions(&mut qb);
+    qb.push(" ORDER BY created_at DESC, id DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    let jobs: Vec<Job> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    Ok(Json(json!({
+        "data": jobs,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": (total + limit as i64 - 1) / limit as i64
+        }
+    })))
+}
+
+// Puts a dead job back in the queue with a fresh set of attempts
+pub async fn retry_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Job>> {
+    let job_id =
+        Uuid::parse_str(&id).map_err(|_| AppError::BadRequest("Invalid job ID".to_string()))?;
+
+    let status: String = sqlx::query_scalar("SELECT status FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    if status != "dead" {
+        return Err(AppError::Conflict(format!(
+            "Only dead jobs can be retried, this job is {}",
+            status
+        )));
+    }
+
+    let job: Job = sqlx::query_as(
+        "UPDATE jobs SET status = 'pending', attempts = 0, run_at = NOW(), locked_at = NULL, updated_at = NOW()
+         WHERE id = $1 AND status = 'dead'
+         RETURNING *",
+    )
+    .bind(job_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::Conflict("Job was retried concurrently".to_string()))?;
+
+    Ok(Json(job))
+}
@@ -1,13 +1,19 @@
 use crate::error::{AppError, AppResult};
+use crate::filters::SubmissionFilter;
+use crate::leaderboard::{self, LeaderboardQuery};
 use crate::models::*;
+use crate::utils::{academic_year_number, sanitize_file_name};
 use crate::AppState;
 use axum::{
-    extract::{Query, State},
+    extract::{Extension, Path, Query, State},
     http::{header, StatusCode},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     Json,
 };
+use chrono::{Duration, Utc};
 use serde::Deserialize;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[derive(Deserialize)]
@@ -58,20 +64,539 @@ pub async fn get_metrics(
     }))
 }
 
+// Participant rows joined to their submission and hackathon, for breakdowns
+const PARTICIPATION_FROM: &str = "FROM participants p
+     JOIN submissions s ON p.submission_id = s.id
+     JOIN hackathons h ON s.hackathon_id = h.id
+     WHERE 1=1";
+
+#[derive(sqlx::FromRow)]
+struct DepartmentRow {
+    department: String,
+    unique_students: i64,
+    teams: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct DepartmentYearRow {
+    department: String,
+    academic_year: String,
+    unique_students: i64,
+    teams: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct BranchRow {
+    branch: String,
+    registered_students: i64,
+    registered_participants: i64,
+}
+
+fn push_semester(qb: &mut QueryBuilder<'_, Postgres>, semester: &Option<String>) {
+    if let Some(semester) = semester {
+        qb.push(" AND h.semester = ").push_bind(semester.clone());
+    }
+}
+
+// Orders "First Year" before "Second Year" and so on, with unknown values last
+fn sort_years(years: &mut [YearParticipation]) {
+    years.sort_by_key(|y| {
+        (
+            academic_year_number(&y.academic_year).unwrap_or(i32::MAX),
+            y.academic_year.clone(),
+        )
+    });
+}
+
+// Participation by department and academic year. Departments are matched to
+// student branches by name to work out participation rates.
+pub async fn get_department_metrics(
+    State(state): State<AppState>,
+    Query(query): Query<MetricsQuery>,
+) -> AppResult<Json<DepartmentMetrics>> {
+    let mut qb = QueryBuilder::<Postgres>::new(format!(
+        "SELECT MIN(TRIM(p.department)) AS department,
+                COUNT(DISTINCT LOWER(p.email)) AS unique_students,
+                COUNT(DISTINCT p.submission_id) AS teams
+         {}",
+        PARTICIPATION_FROM
+    ));
+    push_semester(&mut qb, &query.semester);
+    // Spellings that differ only in case or padding are one department
+    qb.push(" GROUP BY LOWER(TRIM(p.department))");
+    let department_rows: Vec<DepartmentRow> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    let mut qb = QueryBuilder::<Postgres>::new(format!(
+        "SELECT MIN(TRIM(p.department)) AS department, p.academic_year,
+                COUNT(DISTINCT LOWER(p.email)) AS unique_students,
+                COUNT(DISTINCT p.submission_id) AS teams
+         {}",
+        PARTICIPATION_FROM
+    ));
+    push_semester(&mut qb, &query.semester);
+    qb.push(" GROUP BY LOWER(TRIM(p.department)), p.academic_year");
+    let department_year_rows: Vec<DepartmentYearRow> =
+        qb.build_query_as().fetch_all(&state.db).await?;
+
+    let mut qb = QueryBuilder::<Postgres>::new(format!(
+        "SELECT p.academic_year, COUNT(DISTINCT LOWER(p.email)) AS unique_students,
+                COUNT(DISTINCT p.submission_id) AS teams
+         {}",
+        PARTICIPATION_FROM
+    ));
+    push_semester(&mut qb, &query.semester);
+    qb.push(" GROUP BY p.academic_year");
+    let mut years: Vec<YearParticipation> = qb.build_query_as().fetch_all(&state.db).await?;
+    sort_years(&mut years);
+
+    let mut qb = QueryBuilder::<Postgres>::new(format!(
+        "SELECT MIN(TRIM(st.branch)) AS branch, COUNT(*) AS registered_students,
+                COUNT(*) FILTER (WHERE EXISTS (
+                    SELECT 1 {} AND LOWER(p.email) = LOWER(st.email)",
+        PARTICIPATION_FROM
+    ));
+    push_semester(&mut qb, &query.semester);
+    qb.push(")) AS registered_participants FROM students st GROUP BY LOWER(TRIM(st.branch))");
+    let branch_rows: Vec<BranchRow> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    let mut departments: BTreeMap<String, DepartmentParticipation> = BTreeMap::new();
+    for row in department_rows {
+        departments.insert(
+            row.department.to_lowercase(),
+            DepartmentParticipation {
+                department: row.department,
+                unique_students: row.unique_students,
+                teams: row.teams,
+                registered_students: 0,
+                registered_participants: 0,
+                participation_rate: None,
+                years: Vec::new(),
+            },
+        );
+    }
+
+    for row in department_year_rows {
+        if let Some(department) = departments.get_mut(&row.department.to_lowercase()) {
+            department.years.push(YearParticipation {
+                academic_year: row.academic_year,
+                unique_students: row.unique_students,
+                teams: row.teams,
+            });
+        }
+    }
+
+    // Branches with registered students but no participants still show up
+    for row in branch_rows {
+        let department = departments
+            .entry(row.branch.to_lowercase())
+            .or_insert_with(|| DepartmentParticipation {
+                department: row.branch.clone(),
+                unique_students: 0,
+                teams: 0,
+                registered_students: 0,
+                registered_participants: 0,
+                participation_rate: None,
+                years: Vec::new(),
+            });
+        department.registered_students += row.registered_students;
+        department.registered_participants += row.registered_participants;
+    }
+
+    let mut departments: Vec<DepartmentParticipation> = departments.into_values().collect();
+    for department in &mut departments {
+        if department.registered_students > 0 {
+            department.participation_rate = Some(
+                department.registered_participants as f64 / department.registered_students as f64,
+            );
+        }
+        sort_years(&mut department.years);
+    }
+    departments.sort_by(|a, b| {
+        b.unique_students
+            .cmp(&a.unique_students)
+            .then_with(|| a.department.cmp(&b.department))
+    });
+
+    Ok(Json(DepartmentMetrics {
+        semester: query.semester,
+        departments,
+        years,
+    }))
+}
+
 #[derive(Deserialize)]
-pub struct ExportQuery {
+pub struct TimeSeriesQuery {
+    pub granularity: Option<String>,
+    pub mode: Option<String>,
+}
+
+const MAX_TIME_SERIES_BUCKETS: i64 = 400;
+
+fn push_mode(qb: &mut QueryBuilder<'_, Postgres>, mode: &Option<String>) {
+    if let Some(mode) = mode {
+        qb.push(" AND h.mode = ").push_bind(mode.clone());
+    }
+}
+
+// Submissions and unique participants per week or month, plus a
+// semester-over-semester comparison. Accepts the usual submission filters;
+// the range defaults to the last year.
+pub async fn get_time_series_metrics(
+    State(state): State<AppState>,
+    Query(filter): Query<SubmissionFilter>,
+    Query(query): Query<TimeSeriesQuery>,
+) -> AppResult<Json<TimeSeriesMetrics>> {
+    let granularity = match query.granularity.as_deref().unwrap_or("week") {
+        "week" => "week",
+        "month" => "month",
+        other => {
+            return Err(AppError::BadRequest(format!(
+                "Invalid granularity: {}. Use week or month",
+                other
+            )))
+        }
+    };
+
+    let mode = match query.mode.as_deref().map(str::to_uppercase) {
+        Some(mode) if mode != "ONLINE" && mode != "OFFLINE" => {
+            return Err(AppError::BadRequest(format!(
+                "Invalid mode: {}. Use ONLINE or OFFLINE",
+                mode
+            )))
+        }
+        mode => mode,
+    };
+
+    let to = filter.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = filter.from.unwrap_or(to - Duration::days(365));
+    let days = (to - from).num_days();
+    let buckets = if granularity == "week" {
+        days / 7
+    } else {
+        days / 28
+    } + 1;
+    if buckets > MAX_TIME_SERIES_BUCKETS {
+        return Err(AppError::BadRequest(format!(
+            "Date range is too long for {} buckets",
+            granularity
+        )));
+    }
+
+    let mut range_filter = filter.clone();
+    range_filter.from = Some(from);
+    range_filter.to = Some(to);
+
+    let mut qb = QueryBuilder::<Postgres>::new(
+        "WITH filtered AS (
+            SELECT s.id, s.created_at FROM submissions s
+            JOIN hackathons h ON s.hackathon_id = h.id
+            WHERE 1=1",
+    );
+    range_filter.push_conditions(&mut qb)?;
+    push_mode(&mut qb, &mode);
+    qb.push(format!(
+        "), buckets AS (SELECT generate_series(date_trunc('{}', ",
+        granularity
+    ))
+    .push_bind(from)
+    .push(format!("::timestamptz), date_trunc('{}', ", granularity))
+    .push_bind(to)
+    .push(format!(
+        "::timestamptz), INTERVAL '1 {}') AS period_start)
+         SELECT b.period_start, COUNT(DISTINCT f.id) AS submissions,
+                COUNT(DISTINCT LOWER(p.email)) AS unique_participants
+         FROM buckets b
+         LEFT JOIN filtered f ON date_trunc('{}', f.created_at) = b.period_start
+         LEFT JOIN participants p ON p.submission_id = f.id",
+        granularity, granularity
+    ));
+    // With a department filter, only that department's participants are counted
+    if let Some(department) = &filter.department {
+        qb.push(" AND LOWER(TRIM(p.department)) = LOWER(TRIM(")
+            .push_bind(department.clone())
+            .push("))");
+    }
+    qb.push(" GROUP BY b.period_start ORDER BY b.period_start");
+    let buckets: Vec<TimeSeriesBucket> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    // Semesters are compared whole, so the date range doesn't apply here
+    let mut semester_filter = filter.clone();
+    semester_filter.from = None;
+    semester_filter.to = None;
+
+    let mut qb = QueryBuilder::<Postgres>::new(
+        "SELECT h.semester, COUNT(DISTINCT h.id) AS hackathons, COUNT(DISTINCT s.id) AS submissions,
+                COUNT(DISTINCT LOWER(p.email)) AS unique_participants
+         FROM submissions s
+         JOIN hackathons h ON s.hackathon_id = h.id
+         LEFT JOIN participants p ON p.submission_id = s.id",
+    );
+    if let Some(department) = &filter.department {
+        qb.push(" AND LOWER(TRIM(p.department)) = LOWER(TRIM(")
+            .push_bind(department.clone())
+            .push("))");
+    }
+    qb.push(" WHERE 1=1");
+    semester_filter.push_conditions(&mut qb)?;
+    push_mode(&mut qb, &mode);
+    qb.push(" GROUP BY h.semester ORDER BY MIN(h.start_date), h.semester");
+    let mut semesters: Vec<SemesterComparison> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    let mut previous: Option<i64> = None;
+    for semester in &mut semesters {
+        semester.submissions_change = previous
+            .filter(|p| *p > 0)
+            .map(|p| (semester.submissions - p) as f64 * 100.0 / p as f64);
+        previous = Some(semester.submissions);
+    }
+
+    Ok(Json(TimeSeriesMetrics {
+        granularity: granularity.to_string(),
+        from,
+        to,
+        buckets,
+        semesters,
+    }))
+}
+
+pub async fn get_hackathon_stats(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<HackathonStats>> {
+    let hackathon_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))?;
+
+    let hackathon: Hackathon = sqlx::query_as("SELECT * FROM hackathons WHERE id = $1")
+        .bind(hackathon_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Hackathon not found".to_string()))?;
+
+    let teams_by_status: Vec<LabelCount> = sqlx::query_as(
+        "SELECT status AS label, COUNT(*) AS count FROM submissions
+         WHERE hackathon_id = $1
+         GROUP BY status ORDER BY count DESC, status",
+    )
+    .bind(hackathon_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let (participants, unique_participants): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*), COUNT(DISTINCT LOWER(p.email))
+         FROM participants p JOIN submissions s ON p.submission_id = s.id
+         WHERE s.hackathon_id = $1",
+    )
+    .bind(hackathon_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let mentors: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM mentors m JOIN submissions s ON m.submission_id = s.id
+         WHERE s.hackathon_id = $1",
+    )
+    .bind(hackathon_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let departments: Vec<LabelCount> = sqlx::query_as(
+        "SELECT p.department AS label, COUNT(*) AS count
+         FROM participants p JOIN submissions s ON p.submission_id = s.id
+         WHERE s.hackathon_id = $1
+         GROUP BY p.department ORDER BY count DESC, p.department",
+    )
+    .bind(hackathon_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut academic_years: Vec<LabelCount> = sqlx::query_as(
+        "SELECT p.academic_year AS label, COUNT(*) AS count
+         FROM participants p JOIN submissions s ON p.submission_id = s.id
+         WHERE s.hackathon_id = $1
+         GROUP BY p.academic_year",
+    )
+    .bind(hackathon_id)
+    .fetch_all(&state.db)
+    .await?;
+    academic_years.sort_by_key(|y| {
+        (
+            academic_year_number(&y.label).unwrap_or(i32::MAX),
+            y.label.clone(),
+        )
+    });
+
+    let outcomes: Vec<LabelCount> = sqlx::query_as(
+        "SELECT r.outcome AS label, COUNT(*) AS count
+         FROM submission_results r JOIN submissions s ON r.submission_id = s.id
+         WHERE s.hackathon_id = $1
+         GROUP BY r.outcome ORDER BY count DESC, r.outcome",
+    )
+    .bind(hackathon_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let count_of = |status: &str| {
+        teams_by_status
+            .iter()
+            .find(|s| s.label == status)
+            .map_or(0, |s| s.count)
+    };
+    let teams: i64 = teams_by_status.iter().map(|s| s.count).sum();
+    let reviewable = teams - count_of("withdrawn");
+    let verification_rate =
+        (reviewable > 0).then(|| count_of("verified") as f64 / reviewable as f64);
+    let average_team_size = (teams > 0).then(|| participants as f64 / teams as f64);
+
+    Ok(Json(HackathonStats {
+        hackathon_id,
+        name: hackathon.name,
+        teams,
+        teams_by_status,
+        participants,
+        unique_participants,
+        mentors,
+        average_team_size,
+        team_capacity: hackathon.team_capacity,
+        verification_rate,
+        departments,
+        academic_years,
+        outcomes,
+    }))
+}
+
+// Admin view of the leaderboard, including students who opted out
+pub async fn get_leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> AppResult<Json<Vec<LeaderboardEntry>>> {
+    let entries = leaderboard::fetch(&state.db, &query, true).await?;
+    Ok(Json(entries))
+}
+
+#[derive(Deserialize)]
+pub struct MentorReportQuery {
     pub semester: Option<String>,
     pub hackathon_id: Option<String>,
     pub format: Option<String>,
 }
 
-pub async fn export_data(
+// Mentors with a faculty account are grouped by account; free-text mentors
+// are matched on a trimmed, case-insensitive name within a department.
+// Declined requests and withdrawn or rejected teams don't count towards a
+// mentor's workload.
+fn push_mentor_filters(
+    qb: &mut QueryBuilder<Postgres>,
+    semester: Option<&String>,
+    hackathon_id: Option<Uuid>,
+) {
+    qb.push(
+        " FROM mentors m
+         JOIN submissions s ON m.submission_id = s.id
+         JOIN hackathons h ON s.hackathon_id = h.id
+         WHERE s.status NOT IN ('withdrawn', 'rejected') AND m.status <> 'declined'",
+    );
+    if let Some(semester) = semester {
+        qb.push(" AND h.semester = ").push_bind(semester.clone());
+    }
+    if let Some(hackathon_id) = hackathon_id {
+        qb.push(" AND h.id = ").push_bind(hackathon_id);
+    }
+}
+
+pub async fn get_mentor_report(
     State(state): State<AppState>,
-    Query(query): Query<ExportQuery>,
-) -> AppResult<impl IntoResponse> {
-    let format = query.format.as_deref().unwrap_or("csv");
+    Query(query): Query<MentorReportQuery>,
+) -> AppResult<Response> {
+    let format = query.format.as_deref().unwrap_or("json");
+    if !["json", "csv", "xlsx"].contains(&format) {
+        return Err(AppError::BadRequest(
+            "Format must be json, csv or xlsx".to_string(),
+        ));
+    }
+
+    let hackathon_id = query
+        .hackathon_id
+        .as_deref()
+        .map(Uuid::parse_str)
+        .transpose()
+        .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))?;
+
+    let mut qb = QueryBuilder::<Postgres>::new(
+        "SELECT MIN(TRIM(m.name)) AS name, MIN(TRIM(m.department)) AS department, h.semester,
+                COUNT(DISTINCT s.id) AS teams, COUNT(DISTINCT h.id) AS hackathons",
+    );
+    push_mentor_filters(&mut qb, query.semester.as_ref(), hackathon_id);
+    qb.push(
+        " GROUP BY COALESCE(m.faculty_id::text, LOWER(TRIM(m.name))), LOWER(TRIM(m.department)),
+                   h.semester
+         ORDER BY h.semester DESC, teams DESC, name",
+    );
+    let mentors: Vec<MentorWorkload> = qb.build_query_as().fetch_all(&state.db).await?;
 
-    let mut sql = r#"
+    let mut qb = QueryBuilder::<Postgres>::new(
+        "SELECT MIN(TRIM(m.department)) AS department, h.semester,
+                COUNT(DISTINCT COALESCE(m.faculty_id::text, LOWER(TRIM(m.name)))) AS mentors, COUNT(DISTINCT s.id) AS teams",
+    );
+    push_mentor_filters(&mut qb, query.semester.as_ref(), hackathon_id);
+    qb.push(
+        " GROUP BY LOWER(TRIM(m.department)), h.semester
+         ORDER BY h.semester DESC, mentors DESC, department",
+    );
+    let departments: Vec<DepartmentMentorCount> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    let report = MentorReport {
+        semester: query.semester,
+        mentors,
+        departments,
+    };
+
+    let (data, content_type) = match format {
+        "json" => return Ok(Json(report).into_response()),
+        "xlsx" => (
+            crate::export::generate_mentor_report_xlsx(&report)?,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        ),
+        _ => (
+            crate::export::generate_mentor_report_csv(&report)?,
+            "text/csv",
+        ),
+    };
+
+    let filename = match &report.semester {
+        Some(semester) => sanitize_file_name(&format!("mentor-report-{}.{}", semester, format)),
+        None => format!("mentor-report.{}", format),
+    };
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+    // Comma-separated, in output order
+    pub columns: Option<String>,
+    pub layout: Option<String>,
+    // Saved preset to start from; explicit parameters override it
+    pub preset: Option<String>,
+}
+
+// Shared by the CSV/XLSX export and the PDF semester report
+async fn fetch_submission_details(
+    db: &PgPool,
+    filter: &SubmissionFilter,
+) -> AppResult<Vec<SubmissionDetail>> {
+    let mut qb = QueryBuilder::<Postgres>::new(
+        r#"
         SELECT 
             s.id as submission_id,
             s.created_at as submitted_at,
@@ -87,51 +612,98 @@ pub async fn export_data(
             STRING_AGG(DISTINCT m.name, ', ') as mentor_names,
             STRING_AGG(DISTINCT m.department, ', ') as mentor_departments,
             s.external_registration_confirmed as external_confirmed,
-            s.status
+            s.status,
+            r.outcome as result_outcome,
+            r.rank as result_rank,
+            r.award_title,
+            r.prize_amount,
+            r.certificate_ref,
+            s.answers
         FROM submissions s
         JOIN hackathons h ON s.hackathon_id = h.id
         LEFT JOIN participants p ON s.id = p.submission_id
-        LEFT JOIN mentors m ON s.id = m.submission_id
+        LEFT JOIN mentors m ON s.id = m.submission_id AND m.status <> 'declined'
+        LEFT JOIN submission_results r ON s.id = r.submission_id
         WHERE 1=1
-    "#
-    .to_string();
+    "#,
+    );
 
-    if query.semester.is_some() {
-        sql.push_str(" AND h.semester = $1");
-    }
+    filter.push_conditions(&mut qb)?;
 
-    if query.hackathon_id.is_some() {
-        let param = if query.semester.is_some() { "$2" } else { "$1" };
-        sql.push_str(&format!(" AND h.id = {}", param));
-    }
+    qb.push(" GROUP BY s.id, s.created_at, h.semester, h.name, s.team_name, s.participant_count, s.mentor_count, p.name, p.email, p.department, p.academic_year, s.external_registration_confirmed, s.status, r.outcome, r.rank, r.award_title, r.prize_amount, r.certificate_ref, s.answers ORDER BY s.created_at DESC");
+
+    Ok(qb.build_query_as().fetch_all(db).await?)
+}
 
-    sql.push_str(" GROUP BY s.id, s.created_at, h.semester, h.name, s.team_name, s.participant_count, s.mentor_count, p.name, p.email, p.department, p.academic_year, s.external_registration_confirmed, s.status ORDER BY s.created_at DESC");
+// Accepts the same filters as the admin submission list
+pub async fn export_data(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(filter): Query<SubmissionFilter>,
+    Query(query): Query<ExportQuery>,
+) -> AppResult<impl IntoResponse> {
+    let preset = match &query.preset {
+        Some(id) => {
+            Some(crate::handlers::export_presets::find_preset(&state.db, &claims, id).await?)
+        }
+        None => None,
+    };
 
-    let mut q = sqlx::query_as::<_, SubmissionDetail>(&sql);
+    let columns = match (&query.columns, &preset) {
+        (Some(columns), _) => crate::export::parse_columns(columns),
+        (None, Some(preset)) => preset.columns.clone(),
+        (None, None) => crate::export::default_columns(),
+    };
+    let layout = query
+        .layout
+        .as_deref()
+        .or(preset.as_ref().map(|p| p.layout.as_str()))
+        .unwrap_or("participant");
+    let format = query
+        .format
+        .as_deref()
+        .or(preset.as_ref().map(|p| p.format.as_str()))
+        .unwrap_or("csv");
 
-    if let Some(sem) = &query.semester {
-        q = q.bind(sem);
-    }
-    if let Some(hid) = &query.hackathon_id {
-        q = q.bind(
-            Uuid::parse_str(hid).map_err(|_| AppError::BadRequest("Invalid ID".to_string()))?,
-        );
-    }
+    crate::export::validate_columns(&columns)?;
+    crate::export::validate_layout(layout)?;
+    crate::export::validate_format(format)?;
+
+    let content_type = if format == "xlsx" {
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    } else {
+        "text/csv"
+    };
 
-    let submissions = q.fetch_all(&state.db).await?;
+    let submissions = fetch_submission_details(&state.db, &filter).await?;
+
+    let hackathon_name: Option<String> = match filter.hackathon_uuid()? {
+        Some(hackathon_id) => {
+            sqlx::query_scalar("SELECT name FROM hackathons WHERE id = $1")
+                .bind(hackathon_id)
+                .fetch_optional(&state.db)
+                .await?
+        }
+        None => None,
+    };
 
     let data = if format == "xlsx" {
-        crate::export::generate_xlsx(submissions)?
+        crate::export::generate_xlsx(&submissions, &columns, layout)?
     } else {
-        crate::export::generate_csv(submissions)?
+        crate::export::generate_csv(&submissions, &columns, layout)?
     };
 
-    let filename = format!("export.{}", format);
-    let content_type = if format == "xlsx" {
-        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    let prefix = if layout == "team" {
+        "teams"
     } else {
-        "text/csv"
+        "submissions"
     };
+    let filename = filter.export_file_name(
+        prefix,
+        hackathon_name.as_deref(),
+        Utc::now().date_naive(),
+        format,
+    );
 
     let response = (
         StatusCode::OK,
@@ -147,3 +719,37 @@ pub async fn export_data(
 
     Ok(response)
 }
+
+// PDF summary of a semester's participation for NAAC/NBA documentation
+pub async fn get_semester_report(
+    State(state): State<AppState>,
+    Query(filter): Query<SubmissionFilter>,
+) -> AppResult<impl IntoResponse> {
+    let semester = filter
+        .semester
+        .clone()
+        .filter(|semester| !semester.trim().is_empty())
+        .ok_or_else(|| AppError::BadRequest("Semester is required".to_string()))?;
+
+    let submissions = fetch_submission_details(&state.db, &filter).await?;
+
+    let filename = sanitize_file_name(&format!("participation-report-{}.pdf", semester));
+
+    let data = tokio::task::spawn_blocking(move || {
+        crate::pdf::generate_semester_report(&semester, &submissions)
+    })
+    .await
+    .map_err(|e| AppError::InternalError(format!("Report rendering failed: {}", e)))??;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        data,
+    ))
+}
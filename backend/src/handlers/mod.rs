@@ -1,4 +1,11 @@
 pub mod admin;
+pub mod certificates;
+pub mod events;
+pub mod export_presets;
+pub mod faculty;
+pub mod jobs;
+pub mod notifications;
+pub mod webhooks;
 pub mod public;
 pub mod student;
 pub mod metrics;
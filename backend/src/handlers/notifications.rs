@@ -0,0 +1,181 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    Claims, Notification, NotificationPreferences, UpdateNotificationPreferencesRequest,
+    NOTIFICATION_MODES,
+};
+use crate::AppState;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationListQuery {
+    pub unread: Option<bool>,
+    pub page: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+fn student_id(claims: &Claims) -> AppResult<Uuid> {
+    Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid student ID in token".to_string()))
+}
+
+pub async fn list_notifications(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<NotificationListQuery>,
+) -> AppResult<Json<serde_json::Value>> {
+    let student_id = student_id(&claims)?;
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+    let unread_only = query.unread.unwrap_or(false);
+
+    let push_conditions = |qb: &mut QueryBuilder<'_, Postgres>| {
+        qb.push(" WHERE student_id = ").push_bind(student_id);
+        if unread_only {
+            qb.push(" AND read_at IS NULL");
+        }
+    };
+
+    let mut count_qb = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM notifications");
+    push_conditions(&mut count_qb);
+    let total: i64 = count_qb.build_query_scalar().fetch_one(&state.db).await?;
+
+    let mut qb = QueryBuilder::<Postgres>::new("SELECT * FROM notifications");
+    push_conditions(&mut qb);
+    qb.push(" ORDER BY created_at DESC, id DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    let notifications: Vec<Notification> = qb.build_query_as().fetch_all(&state.db).await?;
+
+    Ok(Json(json!({
+        "data": notifications,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": (total + limit as i64 - 1) / limit as i64
+        }
+    })))
+}
+
+pub async fn unread_count(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<serde_json::Value>> {
+    let student_id = student_id(&claims)?;
+
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE student_id = $1 AND read_at IS NULL",
+    )
+    .bind(student_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(json!({ "unread": count })))
+}
+
+pub async fn mark_read(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Notification>> {
+    let student_id = student_id(&claims)?;
+    let notification_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid notification ID".to_string()))?;
+
+    let notification: Notification = sqlx::query_as(
+        "UPDATE notifications SET read_at = COALESCE(read_at, NOW())
+         WHERE id = $1 AND student_id = $2
+         RETURNING *",
+    )
+    .bind(notification_id)
+    .bind(student_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Notification not found".to_string()))?;
+
+    Ok(Json(notification))
+}
+
+pub async fn mark_all_read(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<serde_json::Value>> {
+    let student_id = student_id(&claims)?;
+
+    let result = sqlx::query(
+        "UPDATE notifications SET read_at = NOW() WHERE student_id = $1 AND read_at IS NULL",
+    )
+    .bind(student_id)
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(json!({ "updated": result.rows_affected() })))
+}
+
+pub async fn get_preferences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<NotificationPreferences>> {
+    let student_id = student_id(&claims)?;
+
+    let preferences: NotificationPreferences = sqlx::query_as(
+        "SELECT notify_submission AS submission, notify_hackathon AS hackathon, notify_blog AS blog
+         FROM students WHERE id = $1",
+    )
+    .bind(student_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Student not found".to_string()))?;
+
+    Ok(Json(preferences))
+}
+
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<UpdateNotificationPreferencesRequest>,
+) -> AppResult<Json<NotificationPreferences>> {
+    let student_id = student_id(&claims)?;
+
+    for mode in [&req.submission, &req.hackathon, &req.blog]
+        .into_iter()
+        .flatten()
+    {
+        if !NOTIFICATION_MODES.contains(&mode.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid notification mode: {}. Use one of: {}",
+                mode,
+                NOTIFICATION_MODES.join(", ")
+            )));
+        }
+    }
+
+    let preferences: NotificationPreferences = sqlx::query_as(
+        "UPDATE students SET
+            notify_submission = COALESCE($2, notify_submission),
+            notify_hackathon = COALESCE($3, notify_hackathon),
+            notify_blog = COALESCE($4, notify_blog),
+            updated_at = NOW()
+         WHERE id = $1
+         RETURNING notify_submission AS submission, notify_hackathon AS hackathon, notify_blog AS blog",
+    )
+    .bind(student_id)
+    .bind(&req.submission)
+    .bind(&req.hackathon)
+    .bind(&req.blog)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Student not found".to_string()))?;
+
+    Ok(Json(preferences))
+}
@@ -1,13 +1,27 @@
+use crate::eligibility::check_team;
 use crate::error::{AppError, AppResult};
+use crate::events;
+use crate::forms::validate_answers;
+use crate::jobs;
+use crate::leaderboard::{self, LeaderboardQuery};
 use crate::models::*;
-use crate::utils::validate_srec_email;
+use crate::receipts::submission_receipts;
+use crate::spam::ClientIp;
+use crate::submissions::{
+    find_identical, initial_status, lock_submission, set_status, waitlist_position,
+};
+use crate::utils::{
+    detect_document_type, sanitize_file_name, validate_srec_email, ALLOWED_DOCUMENT_TYPES,
+};
 use axum::{
-    extract::{Path, Query, State},
+    extract::{multipart::MultipartError, Multipart, Path, Query, State},
     http::StatusCode,
     Json,
 };
 use serde::Deserialize;
 use serde_json::json;
+use sqlx::types::JsonValue;
+use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::AppState;
@@ -78,6 +92,114 @@ pub async fn get_hackathon_by_slug(
     Ok(Json(hackathon))
 }
 
+pub async fn list_hackathon_winners(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Vec<HackathonWinner>>> {
+    let hackathon_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))?;
+
+    let _: (Uuid,) = sqlx::query_as("SELECT id FROM hackathons WHERE id = $1")
+        .bind(hackathon_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Hackathon not found".to_string()))?;
+
+    // Participant names only; emails are not exposed publicly
+    let winners: Vec<HackathonWinner> = sqlx::query_as(
+        "SELECT s.id as submission_id, s.team_name, r.outcome, r.rank, r.award_title, r.prize_amount,
+                COALESCE(ARRAY_AGG(p.name::text ORDER BY p.name) FILTER (WHERE p.name IS NOT NULL), '{}') as participants
+         FROM submission_results r
+         JOIN submissions s ON r.submission_id = s.id
+         LEFT JOIN participants p ON p.submission_id = s.id
+         WHERE s.hackathon_id = $1
+         GROUP BY s.id, s.team_name, r.outcome, r.rank, r.award_title, r.prize_amount
+         ORDER BY CASE r.outcome
+                    WHEN 'winner' THEN 1
+                    WHEN 'runner_up' THEN 2
+                    WHEN 'finalist' THEN 3
+                    ELSE 4
+                  END,
+                  r.rank NULLS LAST,
+                  s.team_name",
+    )
+    .bind(hackathon_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(winners))
+}
+
+#[derive(Deserialize)]
+pub struct FacultyDirectoryQuery {
+    pub department: Option<String>,
+}
+
+// Active faculty that teams can choose as mentors
+pub async fn list_faculty(
+    State(state): State<AppState>,
+    Query(query): Query<FacultyDirectoryQuery>,
+) -> AppResult<Json<Vec<FacultyPublic>>> {
+    let faculty: Vec<FacultyPublic> = sqlx::query_as(
+        "SELECT id, name, department, designation FROM faculty
+         WHERE active AND ($1::text IS NULL OR department = $1)
+         ORDER BY department, name",
+    )
+    .bind(query.department)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(faculty))
+}
+
+// Students who opted out are left off, and emails are never shown
+pub async fn get_leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> AppResult<Json<Vec<LeaderboardEntry>>> {
+    let entries = leaderboard::fetch(&state.db, &query, false).await?;
+    Ok(Json(entries))
+}
+
+// Public summary for the hackathon page. Only teams holding a seat are counted.
+pub async fn get_hackathon_stats(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<PublicHackathonStats>> {
+    let hackathon_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))?;
+
+    let _: (Uuid,) = sqlx::query_as("SELECT id FROM hackathons WHERE id = $1")
+        .bind(hackathon_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Hackathon not found".to_string()))?;
+
+    let teams: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM submissions
+         WHERE hackathon_id = $1 AND status IN ('submitted', 'verified')",
+    )
+    .bind(hackathon_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let departments: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT p.department
+         FROM participants p JOIN submissions s ON p.submission_id = s.id
+         WHERE s.hackathon_id = $1 AND s.status IN ('submitted', 'verified')
+         ORDER BY p.department",
+    )
+    .bind(hackathon_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PublicHackathonStats {
+        hackathon_id,
+        teams,
+        departments,
+    }))
+}
+
 pub async fn list_blog_posts(
     State(state): State<AppState>,
     Query(query): Query<PaginationQuery>,
@@ -128,23 +250,42 @@ pub async fn get_blog_post(
 
 pub async fn submit_participation(
     State(state): State<AppState>,
-    Json(req): Json<SubmitParticipationRequest>,
+    ClientIp(ip): ClientIp,
+    Json(mut req): Json<SubmitParticipationRequest>,
 ) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    // Bots that fill the honeypot get a normal-looking response and nothing is stored
+    if !req.website.trim().is_empty() {
+        tracing::warn!("Honeypot triggered on submission from {:?}", ip);
+        return Ok((
+            StatusCode::CREATED,
+            Json(json!({
+                "submission_id": Uuid::new_v4(),
+                "status": SubmissionStatus::Submitted.as_str(),
+                "waitlist_position": null
+            })),
+        ));
+    }
+
+    if !state
+        .challenge
+        .verify(req.challenge_token.as_deref(), ip)
+        .await?
+    {
+        return Err(AppError::Forbidden("Challenge verification failed".to_string()));
+    }
+
     // Parse and validate hackathon ID
     let hackathon_id = Uuid::parse_str(&req.hackathon_id)
         .map_err(|_| AppError::BadRequest("Invalid hackathon ID".to_string()))?;
 
     // Verify hackathon exists and status is not CLOSED
-    let hackathon: Option<(String, String)> =
-        sqlx::query_as("SELECT status, id::text FROM hackathons WHERE id = $1")
-            .bind(hackathon_id)
-            .fetch_optional(&state.db)
-            .await?;
-
-    let (status, _) = hackathon
+    let hackathon: Hackathon = sqlx::query_as("SELECT * FROM hackathons WHERE id = $1")
+        .bind(hackathon_id)
+        .fetch_optional(&state.db)
+        .await?
         .ok_or_else(|| AppError::NotFound("Hackathon not found".to_string()))?;
 
-    if status == "CLOSED" {
+    if hackathon.status == "CLOSED" {
         return Err(AppError::BadRequest(
             "Hackathon registration is closed".to_string(),
         ));
@@ -160,25 +301,17 @@ pub async fn submit_participation(
     // Validate participant count matches declared count
     let declared_participant_count = req.participants.len() as i32;
     let declared_mentor_count = req.mentors.len() as i32;
-    
-    // Validate counts are reasonable (you can adjust min/max as needed)
-    if declared_participant_count < 1 {
+
+    if req.participants.is_empty() {
         return Err(AppError::BadRequest(
             "At least one participant is required".to_string(),
         ));
     }
-    
-    if declared_participant_count > 10 {
-        return Err(AppError::BadRequest(
-            "Maximum 10 participants per team".to_string(),
-        ));
-    }
-    
-    if declared_mentor_count > 5 {
-        return Err(AppError::BadRequest(
-            "Maximum 5 mentors per team".to_string(),
-        ));
-    }
+
+    let mentor_faculty_ids = resolve_mentors(&state.db, &mut req.mentors).await?;
+
+    // Enforce the hackathon's team size, mentor and eligibility rules
+    check_team(&hackathon, &req.participants, req.mentors.len())?;
 
     // Validate team name
     if req.team_name.trim().is_empty() {
@@ -196,13 +329,39 @@ pub async fn submit_participation(
         }
     }
 
+    // Validate answers to the hackathon's custom registration questions
+    let answers = validate_answers(&hackathon.registration_fields, &req.answers)?;
+
     // Create submission with transaction
     let mut tx = state.db.begin().await?;
 
+    // Teams beyond the hackathon's capacity go on the waitlist
+    let status = initial_status(&mut tx, hackathon_id).await?;
+
+    // The same team submitting again gets its existing registration back
+    let emails: Vec<String> = req.participants.iter().map(|p| p.email.clone()).collect();
+    if let Some((existing_id, existing_status)) =
+        find_identical(&mut tx, hackathon_id, &req.team_name, &emails).await?
+    {
+        let waitlist_position = waitlist_position(&mut tx, existing_id).await?;
+        tx.commit().await?;
+
+        return Ok((
+            StatusCode::OK,
+            Json(json!({
+                "submission_id": existing_id,
+                "status": existing_status,
+                "waitlist_position": waitlist_position,
+                "duplicate": true
+            })),
+        ));
+    }
+
     let submission_id = Uuid::new_v4();
-    sqlx::query(
-        "INSERT INTO submissions (id, hackathon_id, team_name, participant_count, mentor_count, external_registration_confirmed, status) 
-         VALUES ($1, $2, $3, $4, $5, $6, 'submitted')"
+    let submission: Submission = sqlx::query_as(
+        "INSERT INTO submissions (id, hackathon_id, team_name, participant_count, mentor_count, external_registration_confirmed, status, answers, waitlisted_at) 
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $7 = 'waitlisted' THEN NOW() END)
+         RETURNING *"
     )
     .bind(&submission_id)
     .bind(&hackathon_id)
@@ -210,7 +369,9 @@ pub async fn submit_participation(
     .bind(declared_participant_count)
     .bind(declared_mentor_count)
     .bind(true)
-    .execute(&mut *tx)
+    .bind(status.as_str())
+    .bind(JsonValue::Object(answers))
+    .fetch_one(&mut *tx)
     .await?;
 
     // Insert participants
@@ -230,18 +391,42 @@ pub async fn submit_participation(
         .await?;
     }
 
-    // Insert mentors
-    for mentor in &req.mentors {
+    // Insert mentors; faculty picked from the directory have to accept first
+    for (mentor, faculty_id) in req.mentors.iter().zip(&mentor_faculty_ids) {
         let mentor_id = Uuid::new_v4();
-        sqlx::query(
-            "INSERT INTO mentors (id, submission_id, name, department) VALUES ($1, $2, $3, $4)"
+        let mentor: Mentor = sqlx::query_as(
+            "INSERT INTO mentors (id, submission_id, name, department, faculty_id, status)
+             VALUES ($1, $2, $3, $4, $5, CASE WHEN $5 IS NULL THEN 'approved' ELSE 'pending' END)
+             RETURNING *",
         )
         .bind(&mentor_id)
         .bind(&submission_id)
         .bind(&mentor.name)
         .bind(&mentor.department)
-        .execute(&mut *tx)
+        .bind(faculty_id)
+        .fetch_one(&mut *tx)
         .await?;
+
+        if mentor.faculty_id.is_some() {
+            events::mentorship_requested(&mut tx, &mentor).await?;
+        }
+    }
+
+    events::submission_created(&mut tx, &submission).await?;
+
+    let waitlist_position = waitlist_position(&mut tx, submission_id).await?;
+
+    // Receipts go through the job queue and are only sent if the submission commits
+    let receipts = submission_receipts(
+        &state.public_api_url,
+        submission_id,
+        &hackathon,
+        &req,
+        &status,
+        waitlist_position,
+    );
+    for receipt in &receipts {
+        jobs::enqueue(&mut tx, jobs::SEND_EMAIL, receipt).await?;
     }
 
     tx.commit().await?;
@@ -250,7 +435,377 @@ pub async fn submit_participation(
         StatusCode::CREATED,
         Json(json!({
             "submission_id": submission_id,
-            "status": "submitted"
+            "status": status.as_str(),
+            "waitlist_position": waitlist_position
         })),
     ))
 }
+
+// Fills in name and department for mentors picked from the faculty directory
+// and returns the faculty ID of each mentor (None for free-text mentors)
+async fn resolve_mentors(db: &PgPool, mentors: &mut [MentorInput]) -> AppResult<Vec<Option<Uuid>>> {
+    let mut faculty_ids = Vec::with_capacity(mentors.len());
+
+    for mentor in mentors.iter_mut() {
+        let Some(faculty_id) = &mentor.faculty_id else {
+            if mentor.name.trim().is_empty() || mentor.department.trim().is_empty() {
+                return Err(AppError::BadRequest(
+                    "Mentors need a faculty ID or a name and department".to_string(),
+                ));
+            }
+            faculty_ids.push(None);
+            continue;
+        };
+
+        let faculty_id = Uuid::parse_str(faculty_id)
+            .map_err(|_| AppError::BadRequest("Invalid faculty ID".to_string()))?;
+
+        let faculty: Faculty = sqlx::query_as("SELECT * FROM faculty WHERE id = $1 AND active")
+            .bind(faculty_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Faculty member not found".to_string()))?;
+
+        if faculty_ids.contains(&Some(faculty.id)) {
+            return Err(AppError::BadRequest(
+                "The same faculty member is listed twice".to_string(),
+            ));
+        }
+
+        mentor.name = faculty.name;
+        mentor.department = faculty.department;
+        faculty_ids.push(Some(faculty.id));
+    }
+
+    Ok(faculty_ids)
+}
+
+pub const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
+// Leaves room for the multipart framing and text fields around the file
+pub const DOCUMENT_UPLOAD_BODY_LIMIT: usize = MAX_DOCUMENT_SIZE + 64 * 1024;
+const MAX_DOCUMENTS_PER_SUBMISSION: i64 = 10;
+
+// Teams have no accounts, so actions on a submission require the email of one
+// of its participants.
+async fn verify_team_member(db: &PgPool, submission_id: Uuid, email: &str) -> AppResult<()> {
+    let exists: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM submissions WHERE id = $1")
+        .bind(submission_id)
+        .fetch_optional(db)
+        .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound("Submission not found".to_string()));
+    }
+
+    let is_member: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM participants WHERE submission_id = $1 AND LOWER(email) = LOWER($2))",
+    )
+    .bind(submission_id)
+    .bind(email.trim())
+    .fetch_one(db)
+    .await?;
+
+    if !is_member {
+        return Err(AppError::Forbidden(
+            "Email does not belong to a participant of this submission".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn multipart_error(e: MultipartError) -> AppError {
+    AppError::BadRequest(format!("Invalid multipart body: {}", e))
+}
+
+pub async fn upload_submission_document(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> AppResult<(StatusCode, Json<SubmissionDocument>)> {
+    let submission_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
+
+    let mut email = None;
+    let mut kind = None;
+    let mut file = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "email" => email = Some(field.text().await.map_err(multipart_error)?),
+            "kind" => kind = Some(field.text().await.map_err(multipart_error)?),
+            "file" => {
+                let file_name = sanitize_file_name(field.file_name().unwrap_or("document"));
+                let data = field.bytes().await.map_err(multipart_error)?;
+                file = Some((file_name, data));
+            }
+            _ => {}
+        }
+    }
+
+    let email = email.ok_or_else(|| AppError::BadRequest("Email is required".to_string()))?;
+    let kind = kind.unwrap_or_else(|| "registration_proof".to_string());
+    let (file_name, data) =
+        file.ok_or_else(|| AppError::BadRequest("File is required".to_string()))?;
+
+    if !DOCUMENT_KINDS.contains(&kind.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid document kind: {}",
+            kind
+        )));
+    }
+
+    if data.is_empty() {
+        return Err(AppError::BadRequest("Uploaded file is empty".to_string()));
+    }
+
+    if data.len() > MAX_DOCUMENT_SIZE {
+        return Err(AppError::BadRequest(format!(
+            "File exceeds the {} MB limit",
+            MAX_DOCUMENT_SIZE / (1024 * 1024)
+        )));
+    }
+
+    // Trust the file contents, not the client-declared type, which browsers
+    // and upload tools often get wrong (e.g. image/jpg, application/octet-stream)
+    let content_type = detect_document_type(&data).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Unsupported file type; allowed types are {}",
+            ALLOWED_DOCUMENT_TYPES.join(", ")
+        ))
+    })?;
+
+    verify_team_member(&state.db, submission_id, &email).await?;
+
+    let document_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM submission_documents WHERE submission_id = $1",
+    )
+    .bind(submission_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if document_count >= MAX_DOCUMENTS_PER_SUBMISSION {
+        return Err(AppError::BadRequest(format!(
+            "A submission can have at most {} documents",
+            MAX_DOCUMENTS_PER_SUBMISSION
+        )));
+    }
+
+    let document_id = Uuid::new_v4();
+    let extension = match content_type {
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        _ => "jpg",
+    };
+    let storage_key = format!(
+        "submissions/{}/{}.{}",
+        submission_id, document_id, extension
+    );
+
+    state.storage.put(&storage_key, &data).await?;
+
+    let document = sqlx::query_as::<_, SubmissionDocument>(
+        "INSERT INTO submission_documents (id, submission_id, kind, file_name, content_type, size_bytes, storage_key, uploaded_by_email)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING *",
+    )
+    .bind(document_id)
+    .bind(submission_id)
+    .bind(&kind)
+    .bind(&file_name)
+    .bind(content_type)
+    .bind(data.len() as i64)
+    .bind(&storage_key)
+    .bind(email.trim())
+    .fetch_one(&state.db)
+    .await;
+
+    match document {
+        Ok(document) => Ok((StatusCode::CREATED, Json(document))),
+        Err(e) => {
+            // Don't leave an orphaned file behind if the row couldn't be written
+            if let Err(cleanup) = state.storage.delete(&storage_key).await {
+                tracing::warn!(
+                    "Failed to remove orphaned upload {}: {}",
+                    storage_key,
+                    cleanup
+                );
+            }
+            Err(e.into())
+        }
+    }
+}
+
+// Lets a team check where its submission stands, including its waitlist position
+pub async fn get_submission_status(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    let submission_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
+
+    let mut conn = state.db.acquire().await?;
+
+    let (team_name, hackathon_name, status, submitted_at): (
+        String,
+        String,
+        String,
+        chrono::DateTime<chrono::Utc>,
+    ) = sqlx::query_as(
+        "SELECT s.team_name, h.name, s.status, s.created_at
+         FROM submissions s
+         JOIN hackathons h ON s.hackathon_id = h.id
+         WHERE s.id = $1",
+    )
+    .bind(submission_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+
+    let waitlist_position = waitlist_position(&mut conn, submission_id).await?;
+
+    let mentors: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        "SELECT name, status, response_note FROM mentors WHERE submission_id = $1 ORDER BY name",
+    )
+    .bind(submission_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let mentors: Vec<serde_json::Value> = mentors
+        .into_iter()
+        .map(|(name, status, note)| json!({ "name": name, "status": status, "note": note }))
+        .collect();
+
+    Ok(Json(json!({
+        "submission_id": submission_id,
+        "team_name": team_name,
+        "hackathon_name": hackathon_name,
+        "status": status,
+        "waitlist_position": waitlist_position,
+        "submitted_at": submitted_at,
+        "mentors": mentors
+    })))
+}
+
+pub async fn withdraw_submission(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<WithdrawSubmissionRequest>,
+) -> AppResult<Json<Submission>> {
+    let submission_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
+
+    verify_team_member(&state.db, submission_id, &req.email).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let (_, current) = lock_submission(&mut tx, submission_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+
+    if !matches!(current.as_str(), "submitted" | "verified" | "waitlisted") {
+        return Err(AppError::Conflict(format!(
+            "A {} submission cannot be withdrawn",
+            current
+        )));
+    }
+
+    let submission = set_status(&mut tx, submission_id, &SubmissionStatus::Withdrawn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+
+    tx.commit().await?;
+
+    Ok(Json(submission))
+}
+
+// A declined mentor keeps the team's mentor slot until the team asks someone
+// else, so the team can pick another faculty member in its place
+pub async fn replace_mentor(
+    State(state): State<AppState>,
+    Path((id, mentor_id)): Path<(String, String)>,
+    Json(req): Json<ReplaceMentorRequest>,
+) -> AppResult<Json<Mentor>> {
+    let submission_id = Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest("Invalid submission ID".to_string()))?;
+    let mentor_id = Uuid::parse_str(&mentor_id)
+        .map_err(|_| AppError::BadRequest("Invalid mentor ID".to_string()))?;
+    let faculty_id = Uuid::parse_str(&req.faculty_id)
+        .map_err(|_| AppError::BadRequest("Invalid faculty ID".to_string()))?;
+
+    verify_team_member(&state.db, submission_id, &req.email).await?;
+
+    let faculty: Faculty = sqlx::query_as("SELECT * FROM faculty WHERE id = $1 AND active")
+        .bind(faculty_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Faculty member not found".to_string()))?;
+
+    let mut tx = state.db.begin().await?;
+
+    let (_, status) = lock_submission(&mut tx, submission_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+
+    if !matches!(status.as_str(), "submitted" | "verified" | "waitlisted") {
+        return Err(AppError::Conflict(format!(
+            "Mentors of a {} submission cannot be changed",
+            status
+        )));
+    }
+
+    let current: Mentor =
+        sqlx::query_as("SELECT * FROM mentors WHERE id = $1 AND submission_id = $2 FOR UPDATE")
+            .bind(mentor_id)
+            .bind(submission_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Mentor not found".to_string()))?;
+
+    if current.status != "declined" {
+        return Err(AppError::Conflict(
+            "Only a mentor who declined can be replaced".to_string(),
+        ));
+    }
+
+    if current.faculty_id == Some(faculty.id) {
+        return Err(AppError::BadRequest(format!(
+            "{} has already declined this team",
+            faculty.name
+        )));
+    }
+
+    let already_listed: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM mentors WHERE submission_id = $1 AND faculty_id = $2)",
+    )
+    .bind(submission_id)
+    .bind(faculty.id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if already_listed {
+        return Err(AppError::BadRequest(
+            "The same faculty member is listed twice".to_string(),
+        ));
+    }
+
+    let mentor: Mentor = sqlx::query_as(
+        "UPDATE mentors
+         SET faculty_id = $2, name = $3, department = $4, status = 'pending',
+             response_note = NULL, responded_at = NULL
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(mentor_id)
+    .bind(faculty.id)
+    .bind(&faculty.name)
+    .bind(&faculty.department)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    events::mentorship_requested(&mut tx, &mentor).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(mentor))
+}
@@ -1,6 +1,7 @@
 use crate::auth::{create_jwt, hash_password, verify_password};
 use crate::error::{AppError, AppResult};
 use crate::models::*;
+use crate::utils::validate_password;
 use axum::{
     extract::{Extension, Path, Query, State},
     http::StatusCode,
@@ -38,6 +39,8 @@ pub async fn register(
         ));
     }
 
+    validate_password(&req.password)?;
+
     // Hash password
     let password_hash = hash_password(&req.password)?;
 
@@ -377,3 +380,27 @@ pub async fn search(
         "count": students_list.len()
     })))
 }
+
+// Hide or show the student on the public leaderboard
+pub async fn set_leaderboard_opt_out(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<LeaderboardOptOutRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let student_uuid = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid student ID".to_string()))?;
+
+    let result = sqlx::query(
+        "UPDATE students SET leaderboard_opt_out = $1, updated_at = NOW() WHERE id = $2",
+    )
+    .bind(req.opt_out)
+    .bind(student_uuid)
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Student not found".to_string()));
+    }
+
+    Ok(Json(json!({ "opt_out": req.opt_out })))
+}
@@ -0,0 +1,227 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    Claims, CreateWebhookRequest, UpdateWebhookRequest, Webhook, WebhookDelivery, WEBHOOK_EVENTS,
+};
+use crate::webhooks::generate_secret;
+use crate::AppState;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryListQuery {
+    pub status: Option<String>,
+    pub page: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+fn parse_webhook_id(id: &str) -> AppResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| AppError::BadRequest("Invalid webhook ID".to_string()))
+}
+
+fn validate_url(url: &str) -> AppResult<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|_| AppError::BadRequest(format!("Invalid webhook URL: {}", url)))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(AppError::BadRequest(
+            "Webhook URL must be an http(s) URL".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_events(events: &[String]) -> AppResult<()> {
+    if events.is_empty() {
+        return Err(AppError::BadRequest(
+            "Select at least one event".to_string(),
+        ));
+    }
+
+    if let Some(unknown) = events
+        .iter()
+        .find(|e| !WEBHOOK_EVENTS.contains(&e.as_str()))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Unknown webhook event: {}. Use one of: {}",
+            unknown,
+            WEBHOOK_EVENTS.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+// The secret is only returned here; receivers need it to verify X-Coin-Signature
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    validate_url(&req.url)?;
+    validate_events(&req.events)?;
+
+    let created_by = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid admin ID in token".to_string()))?;
+    let secret = match req.secret {
+        Some(secret) if secret.len() < 16 => {
+            return Err(AppError::BadRequest(
+                "Webhook secret must be at least 16 characters".to_string(),
+            ))
+        }
+        Some(secret) => secret,
+        None => generate_secret(),
+    };
+
+    let mut events = req.events;
+    events.sort();
+    events.dedup();
+
+    let webhook: Webhook = sqlx::query_as(
+        "INSERT INTO webhooks (url, secret, events, description, created_by)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING *",
+    )
+    .bind(&req.url)
+    .bind(&secret)
+    .bind(&events)
+    .bind(&req.description)
+    .bind(created_by)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "webhook": webhook,
+            "secret": secret
+        })),
+    ))
+}
+
+pub async fn list_webhooks(State(state): State<AppState>) -> AppResult<Json<Vec<Webhook>>> {
+    let webhooks: Vec<Webhook> = sqlx::query_as("SELECT * FROM webhooks ORDER BY created_at DESC")
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(webhooks))
+}
+
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateWebhookRequest>,
+) -> AppResult<Json<Webhook>> {
+    let webhook_id = parse_webhook_id(&id)?;
+
+    if let Some(url) = &req.url {
+        validate_url(url)?;
+    }
+
+    let events = match req.events {
+        Some(mut events) => {
+            validate_events(&events)?;
+            events.sort();
+            events.dedup();
+            Some(events)
+        }
+        None => None,
+    };
+
+    let webhook: Webhook = sqlx::query_as(
+        "UPDATE webhooks SET
+            url = COALESCE($2, url),
+            events = COALESCE($3, events),
+            description = COALESCE($4, description),
+            active = COALESCE($5, active),
+            updated_at = NOW()
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(webhook_id)
+    .bind(&req.url)
+    .bind(&events)
+    .bind(&req.description)
+    .bind(req.active)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Webhook not found".to_string()))?;
+
+    Ok(Json(webhook))
+}
+
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<StatusCode> {
+    let webhook_id = parse_webhook_id(&id)?;
+
+    let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+        .bind(webhook_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Webhook not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Delivery log for one webhook, newest first
+pub async fn list_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<DeliveryListQuery>,
+) -> AppResult<Json<serde_json::Value>> {
+    let webhook_id = parse_webhook_id(&id)?;
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM webhooks WHERE id = $1)")
+        .bind(webhook_id)
+        .fetch_one(&state.db)
+        .await?;
+    if !exists {
+        return Err(AppError::NotFound("Webhook not found".to_string()));
+    }
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM webhook_deliveries
+         WHERE webhook_id = $1 AND ($2::text IS NULL OR status = $2)",
+    )
+    .bind(webhook_id)
+    .bind(&query.status)
+    .fetch_one(&state.db)
+    .await?;
+
+    let deliveries: Vec<WebhookDelivery> = sqlx::query_as(
+        "SELECT * FROM webhook_deliveries
+         WHERE webhook_id = $1 AND ($2::text IS NULL OR status = $2)
+         ORDER BY created_at DESC, id DESC
+         LIMIT $3 OFFSET $4",
+    )
+    .bind(webhook_id)
+    .bind(&query.status)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(json!({
+        "data": deliveries,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": (total + limit as i64 - 1) / limit as i64
+        }
+    })))
+}
@@ -0,0 +1,223 @@
+use crate::digest;
+use crate::error::{AppError, AppResult};
+use crate::mailer::Email;
+use crate::models::Job;
+use crate::notifications::NotificationEmail;
+use crate::webhooks::{self, DeliveryJob};
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::types::JsonValue;
+use sqlx::PgConnection;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const SEND_EMAIL: &str = "send_email";
+pub const NOTIFICATION_EMAIL: &str = "notification_email";
+pub const WEEKLY_DIGEST: &str = "weekly_digest";
+pub const DELIVER_WEBHOOK: &str = "deliver_webhook";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+// Jobs left `running` longer than this are assumed to belong to a dead worker
+const STALE_LOCK_MINUTES: i32 = 10;
+const MAX_BACKOFF_SECS: i64 = 3600;
+
+// Adds a job on the caller's connection, so when it is a transaction the job
+// only becomes visible if the surrounding change commits.
+pub async fn enqueue<T: Serialize>(
+    conn: &mut PgConnection,
+    kind: &str,
+    payload: &T,
+) -> AppResult<Uuid> {
+    let payload = serde_json::to_value(payload)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode job payload: {}", e)))?;
+
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO jobs (id, kind, payload) VALUES ($1, $2, $3)")
+        .bind(id)
+        .bind(kind)
+        .bind(payload)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(id)
+}
+
+// Like `enqueue`, but does nothing if a job with the same dedupe key already
+// exists. Returns whether a job was added.
+pub async fn enqueue_unique<T: Serialize>(
+    conn: &mut PgConnection,
+    kind: &str,
+    payload: &T,
+    dedupe_key: &str,
+    run_at: DateTime<Utc>,
+) -> AppResult<bool> {
+    let payload = serde_json::to_value(payload)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode job payload: {}", e)))?;
+
+    let result = sqlx::query(
+        "INSERT INTO jobs (id, kind, payload, run_at, dedupe_key) VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (dedupe_key) WHERE dedupe_key IS NOT NULL DO NOTHING",
+    )
+    .bind(Uuid::new_v4())
+    .bind(kind)
+    .bind(payload)
+    .bind(run_at)
+    .bind(dedupe_key)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub fn start_workers(state: AppState, count: usize) {
+    for worker in 0..count {
+        let state = state.clone();
+        tokio::spawn(async move {
+            tracing::info!("Job worker {} started", worker);
+            loop {
+                match run_next(&state).await {
+                    Ok(true) => {}
+                    Ok(false) => tokio::time::sleep(POLL_INTERVAL).await,
+                    Err(e) => {
+                        tracing::error!("Job worker {} error: {}", worker, e);
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                    }
+                }
+            }
+        });
+    }
+}
+
+// Claims and runs one due job; returns false when the queue is empty
+async fn run_next(state: &AppState) -> AppResult<bool> {
+    sqlx::query(
+        "UPDATE jobs SET status = 'pending', locked_at = NULL, updated_at = NOW()
+         WHERE status = 'running' AND locked_at < NOW() - $1 * INTERVAL '1 minute'",
+    )
+    .bind(STALE_LOCK_MINUTES)
+    .execute(&state.db)
+    .await?;
+
+    let job: Option<Job> = sqlx::query_as(
+        "UPDATE jobs
+         SET status = 'running', locked_at = NOW(), attempts = attempts + 1, updated_at = NOW()
+         WHERE id = (
+             SELECT id FROM jobs
+             WHERE status = 'pending' AND run_at <= NOW()
+             ORDER BY run_at
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING *",
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some(job) = job else {
+        return Ok(false);
+    };
+
+    match execute(state, &job).await {
+        Ok(()) => {
+            sqlx::query(
+                "UPDATE jobs SET status = 'completed', locked_at = NULL, last_error = NULL,
+                 completed_at = NOW(), updated_at = NOW()
+                 WHERE id = $1",
+            )
+            .bind(job.id)
+            .execute(&state.db)
+            .await?;
+        }
+        Err(e) => {
+            let error = e.to_string();
+            if job.attempts >= job.max_attempts {
+                tracing::error!(
+                    "Job {} ({}) moved to dead letter: {}",
+                    job.id,
+                    job.kind,
+                    error
+                );
+                sqlx::query(
+                    "UPDATE jobs SET status = 'dead', locked_at = NULL, last_error = $2, updated_at = NOW()
+                     WHERE id = $1",
+                )
+                .bind(job.id)
+                .bind(&error)
+                .execute(&state.db)
+                .await?;
+            } else {
+                let delay = backoff_secs(job.attempts);
+                tracing::warn!(
+                    "Job {} ({}) failed, retrying in {}s: {}",
+                    job.id,
+                    job.kind,
+                    delay,
+                    error
+                );
+                sqlx::query(
+                    "UPDATE jobs SET status = 'pending', locked_at = NULL, last_error = $2,
+                     run_at = NOW() + $3 * INTERVAL '1 second', updated_at = NOW()
+                     WHERE id = $1",
+                )
+                .bind(job.id)
+                .bind(&error)
+                .bind(delay as f64)
+                .execute(&state.db)
+                .await?;
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+// 30s, 60s, 120s, ... capped at an hour
+fn backoff_secs(attempts: i32) -> i64 {
+    let exponent = attempts.clamp(1, 20) as u32 - 1;
+    (30i64 << exponent).min(MAX_BACKOFF_SECS)
+}
+
+async fn execute(state: &AppState, job: &Job) -> AppResult<()> {
+    match job.kind.as_str() {
+        SEND_EMAIL => {
+            let email: Email = decode(&job.payload)?;
+            state.mailer.send(&email).await
+        }
+        NOTIFICATION_EMAIL => {
+            let notification: NotificationEmail = decode(&job.payload)?;
+            state
+                .mailer
+                .send(&notification.into_email(&state.frontend_url))
+                .await
+        }
+        WEEKLY_DIGEST => digest::send_weekly_digest(state, job).await,
+        DELIVER_WEBHOOK => {
+            let delivery: DeliveryJob = decode(&job.payload)?;
+            let final_attempt = job.attempts >= job.max_attempts;
+            webhooks::deliver(state, delivery.delivery_id, final_attempt).await
+        }
+        other => Err(AppError::InternalError(format!(
+            "Unknown job kind: {}",
+            other
+        ))),
+    }
+}
+
+fn decode<T: serde::de::DeserializeOwned>(payload: &JsonValue) -> AppResult<T> {
+    serde_json::from_value(payload.clone())
+        .map_err(|e| AppError::InternalError(format!("Invalid job payload: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_an_hour() {
+        let delays: Vec<i64> = (1..=9).map(backoff_secs).collect();
+        assert_eq!(delays, [30, 60, 120, 240, 480, 960, 1920, 3600, 3600]);
+        assert_eq!(backoff_secs(0), 30);
+        assert_eq!(backoff_secs(100), 3600);
+    }
+}
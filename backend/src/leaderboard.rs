@@ -0,0 +1,74 @@
+use crate::error::{AppError, AppResult};
+use crate::models::LeaderboardEntry;
+use serde::Deserialize;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    pub semester: Option<String>,
+    // participations (default) or wins
+    pub sort: Option<String>,
+    pub limit: Option<i64>,
+}
+
+// Ranks registered students, matched to participants by email. Students who
+// opted out are only included when `include_opted_out` is set (admin view).
+pub async fn fetch(
+    db: &PgPool,
+    query: &LeaderboardQuery,
+    include_opted_out: bool,
+) -> AppResult<Vec<LeaderboardEntry>> {
+    let order = match query.sort.as_deref().unwrap_or("participations") {
+        "participations" => "verified_participations DESC, wins DESC",
+        "wins" => "wins DESC, verified_participations DESC",
+        other => {
+            return Err(AppError::BadRequest(format!(
+                "Invalid sort field: {}. Use participations or wins",
+                other
+            )))
+        }
+    };
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+
+    let mut qb = QueryBuilder::<Postgres>::new(format!(
+        "SELECT RANK() OVER (ORDER BY {}) AS rank, totals.* FROM (
+            SELECT st.id AS student_id, st.name, st.branch, st.year,
+                   st.email, st.leaderboard_opt_out AS opted_out,
+                   COUNT(DISTINCT s.id) FILTER (WHERE s.status = 'verified') AS verified_participations,
+                   COUNT(DISTINCT s.id) FILTER (WHERE r.outcome = 'winner' AND s.status NOT IN ('withdrawn', 'rejected')) AS wins,
+                   COUNT(DISTINCT s.id) FILTER (WHERE r.outcome IS NOT NULL AND s.status NOT IN ('withdrawn', 'rejected')) AS awards
+            FROM students st
+            JOIN participants p ON LOWER(p.email) = LOWER(st.email)
+            JOIN submissions s ON s.id = p.submission_id
+            JOIN hackathons h ON h.id = s.hackathon_id
+            LEFT JOIN submission_results r ON r.submission_id = s.id
+            WHERE 1=1",
+        order
+    ));
+    if let Some(semester) = &query.semester {
+        qb.push(" AND h.semester = ").push_bind(semester.clone());
+    }
+    if !include_opted_out {
+        qb.push(" AND NOT st.leaderboard_opt_out");
+    }
+    qb.push(format!(
+        " GROUP BY st.id
+        ) totals
+        WHERE verified_participations > 0 OR awards > 0
+        ORDER BY {}, name
+        LIMIT ",
+        order
+    ))
+    .push_bind(limit);
+
+    let mut entries: Vec<LeaderboardEntry> = qb.build_query_as().fetch_all(db).await?;
+
+    if !include_opted_out {
+        for entry in &mut entries {
+            entry.email = None;
+            entry.opted_out = None;
+        }
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,85 @@
+use crate::error::AppResult;
+use crate::models::Submission;
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::postgres::PgListener;
+use sqlx::{PgConnection, PgPool};
+use tokio::sync::broadcast;
+
+// Live admin dashboard updates. Events go through Postgres NOTIFY so every
+// server instance sees changes made by any other, and NOTIFY only fires when
+// the surrounding transaction commits.
+pub const ADMIN_EVENTS_CHANNEL: &str = "admin_events";
+const BROADCAST_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct AdminEvent {
+    pub event: String,
+    pub data: String,
+}
+
+#[derive(Deserialize)]
+struct EventEnvelope {
+    event: String,
+}
+
+pub fn channel() -> broadcast::Sender<AdminEvent> {
+    broadcast::channel(BROADCAST_CAPACITY).0
+}
+
+// Payloads stay small (NOTIFY caps them at 8000 bytes); clients refetch details
+pub async fn notify_submission(
+    conn: &mut PgConnection,
+    event: &str,
+    submission: &Submission,
+    previous_status: Option<&str>,
+) -> AppResult<()> {
+    let payload = json!({
+        "event": event,
+        "submission_id": submission.id,
+        "hackathon_id": submission.hackathon_id,
+        "team_name": submission.team_name,
+        "status": submission.status,
+        "previous_status": previous_status,
+    });
+
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(ADMIN_EVENTS_CHANNEL)
+        .bind(payload.to_string())
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+// Forwards NOTIFY payloads to the in-process broadcast channel the SSE handler reads
+pub fn start_listener(pool: PgPool, sender: broadcast::Sender<AdminEvent>) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = listen(&pool, &sender).await {
+                tracing::error!("Admin event listener failed: {}", e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+    });
+}
+
+async fn listen(pool: &PgPool, sender: &broadcast::Sender<AdminEvent>) -> Result<(), sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(ADMIN_EVENTS_CHANNEL).await?;
+
+    loop {
+        let notification = listener.recv().await?;
+        let data = notification.payload().to_string();
+        let Ok(envelope) = serde_json::from_str::<EventEnvelope>(&data) else {
+            tracing::warn!("Ignoring malformed admin event: {}", data);
+            continue;
+        };
+
+        // Sending only fails when no dashboard is connected
+        let _ = sender.send(AdminEvent {
+            event: envelope.event,
+            data,
+        });
+    }
+}
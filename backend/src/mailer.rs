@@ -0,0 +1,124 @@
+use crate::error::{AppError, AppResult};
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: &Email) -> AppResult<()>;
+}
+
+fn build_message(from: &Mailbox, email: &Email) -> AppResult<Message> {
+    let to: Mailbox = email
+        .to
+        .parse()
+        .map_err(|e| AppError::BadRequest(format!("Invalid recipient {}: {}", email.to, e)))?;
+
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(email.subject.clone())
+        .header(ContentType::TEXT_PLAIN)
+        .body(email.body.clone())
+        .map_err(|e| AppError::InternalError(format!("Failed to build email: {}", e)))
+}
+
+pub struct SmtpMailer {
+    from: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: &Email) -> AppResult<()> {
+        let message = build_message(&self.from, email)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::InternalError(format!("SMTP error: {}", e)))?;
+        Ok(())
+    }
+}
+
+// Writes each email to an .eml file instead of sending it, for development
+pub struct FileMailer {
+    from: Mailbox,
+    dir: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(from: Mailbox, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            from,
+            dir: dir.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, email: &Email) -> AppResult<()> {
+        let message = build_message(&self.from, email)?;
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Mail file error: {}", e)))?;
+
+        let file_name = format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S"),
+            Uuid::new_v4()
+        );
+        tokio::fs::write(self.dir.join(file_name), message.formatted())
+            .await
+            .map_err(|e| AppError::InternalError(format!("Mail file error: {}", e)))
+    }
+}
+
+pub fn from_env() -> Result<Arc<dyn Mailer>, Box<dyn std::error::Error>> {
+    let from: Mailbox = std::env::var("MAIL_FROM")
+        .unwrap_or_else(|_| "COIN SREC <no-reply@srec.ac.in>".to_string())
+        .parse()?;
+    let backend = std::env::var("MAIL_BACKEND").unwrap_or_else(|_| "file".to_string());
+
+    match backend.as_str() {
+        "file" => {
+            let dir = std::env::var("MAIL_FILE_PATH").unwrap_or_else(|_| "./mail".to_string());
+            Ok(Arc::new(FileMailer::new(from, dir)))
+        }
+        "smtp" => {
+            let host = std::env::var("SMTP_HOST")?;
+            let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)?;
+            if let Ok(port) = std::env::var("SMTP_PORT") {
+                builder = builder.port(port.parse()?);
+            }
+            if let (Ok(username), Ok(password)) = (
+                std::env::var("SMTP_USERNAME"),
+                std::env::var("SMTP_PASSWORD"),
+            ) {
+                builder = builder.credentials(Credentials::new(username, password));
+            }
+
+            Ok(Arc::new(SmtpMailer {
+                from,
+                transport: builder.build(),
+            }))
+        }
+        other => Err(format!("Unsupported MAIL_BACKEND: {}", other).into()),
+    }
+}
@@ -1,19 +1,38 @@
 mod auth;
+mod certificates;
 mod db;
+mod digest;
+mod eligibility;
 mod error;
+mod events;
 mod export;
+mod filters;
+mod forms;
 mod handlers;
+mod jobs;
+mod leaderboard;
+mod live;
+mod mailer;
 mod middleware;
 mod models;
+mod notifications;
+mod pdf;
+mod receipts;
+mod spam;
+mod storage;
+mod submissions;
 mod utils;
+mod webhooks;
 
 use axum::{
+    extract::DefaultBodyLimit,
     middleware::from_fn,
     middleware::from_fn_with_state,
     routing::{delete, get, patch, post, put},
     Router,
 };
 use sqlx::postgres::PgPoolOptions;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 use tracing_subscriber;
@@ -22,6 +41,17 @@ use tracing_subscriber;
 pub struct AppState {
     pub db: sqlx::PgPool,
     pub jwt_secret: Arc<String>,
+    pub storage: Arc<dyn storage::Storage>,
+    pub rate_limiter: Arc<spam::RateLimiter>,
+    pub challenge: Arc<dyn spam::ChallengeVerifier>,
+    pub trust_proxy_headers: bool,
+    pub mailer: Arc<dyn mailer::Mailer>,
+    pub frontend_url: Arc<String>,
+    // Where this API is reachable from outside, for links in emails and PDFs
+    pub public_api_url: Arc<String>,
+    pub certificate_backgrounds: Arc<certificates::BackgroundCache>,
+    pub http_client: reqwest::Client,
+    pub admin_events: tokio::sync::broadcast::Sender<live::AdminEvent>,
 }
 
 #[tokio::main]
@@ -51,11 +81,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     db::bootstrap_admin(&pool).await?;
 
     let jwt_secret = std::env::var("JWT_SECRET")?;
+    let storage = storage::from_env()?;
+    let rate_limiter = spam::RateLimiter::from_env()?;
+    let challenge = spam::verifier_from_env()?;
+    let trust_proxy_headers = std::env::var("TRUST_PROXY_HEADERS")
+        .map(|v| v == "true")
+        .unwrap_or(false);
+    let mailer = mailer::from_env()?;
+    let frontend_url =
+        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
+    let public_api_url =
+        std::env::var("PUBLIC_API_URL").unwrap_or_else(|_| "http://localhost:8000".to_string());
     let state = AppState {
         db: pool.clone(),
         jwt_secret: Arc::new(jwt_secret),
+        storage,
+        rate_limiter: Arc::new(rate_limiter),
+        challenge,
+        trust_proxy_headers,
+        mailer,
+        frontend_url: Arc::new(frontend_url),
+        public_api_url: Arc::new(public_api_url),
+        certificate_backgrounds: Arc::default(),
+        http_client: reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()?,
+        admin_events: live::channel(),
     };
 
+    // Background job workers
+    let job_workers = std::env::var("JOB_WORKERS")
+        .unwrap_or_else(|_| "2".to_string())
+        .parse()?;
+    jobs::start_workers(state.clone(), job_workers);
+    digest::start_scheduler(state.clone());
+    live::start_listener(pool.clone(), state.admin_events.clone());
+    middleware::start_idempotency_cleanup(pool.clone());
+
     // Public API routes
     let public_routes = Router::new()
         .route("/health", get(handlers::health))
@@ -65,9 +127,53 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             "/hackathons/slug/:slug",
             get(handlers::public::get_hackathon_by_slug),
         )
+        .route(
+            "/hackathons/:id/winners",
+            get(handlers::public::list_hackathon_winners),
+        )
+        .route(
+            "/hackathons/:id/stats",
+            get(handlers::public::get_hackathon_stats),
+        )
+        .route("/leaderboard", get(handlers::public::get_leaderboard))
+        .route("/faculty", get(handlers::public::list_faculty))
+        .route(
+            "/certificates/:code",
+            get(handlers::certificates::verify_certificate),
+        )
+        .route(
+            "/certificates/:code/pdf",
+            get(handlers::certificates::download_certificate),
+        )
         .route("/blog", get(handlers::public::list_blog_posts))
         .route("/blog/:slug", get(handlers::public::get_blog_post))
-        .route("/submit", post(handlers::public::submit_participation));
+        .route(
+            "/submissions/:id/status",
+            get(handlers::public::get_submission_status),
+        );
+
+    // Anonymous forms are rate limited per IP
+    let public_forms = Router::new()
+        .route("/submit", post(handlers::public::submit_participation))
+        .route(
+            "/submissions/:id/withdraw",
+            post(handlers::public::withdraw_submission),
+        )
+        .route(
+            "/submissions/:id/mentors/:mentor_id",
+            put(handlers::public::replace_mentor),
+        )
+        .route(
+            "/submissions/:id/documents",
+            post(handlers::public::upload_submission_document).layer(DefaultBodyLimit::max(
+                handlers::public::DOCUMENT_UPLOAD_BODY_LIMIT,
+            )),
+        )
+        .route_layer(from_fn_with_state(state.clone(), middleware::rate_limit));
+
+    let public_routes = public_routes
+        .merge(public_forms)
+        .layer(from_fn_with_state(state.clone(), middleware::idempotency));
 
     // Student routes
     let student_public = Router::new()
@@ -78,6 +184,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/:id", get(handlers::student::get_profile))
         .route("/:id", put(handlers::student::update_profile))
         .route("/search", get(handlers::student::search))
+        .route(
+            "/leaderboard/opt-out",
+            put(handlers::student::set_leaderboard_opt_out),
+        )
+        .route(
+            "/notifications",
+            get(handlers::notifications::list_notifications),
+        )
+        .route(
+            "/notifications/unread-count",
+            get(handlers::notifications::unread_count),
+        )
+        .route(
+            "/notifications/preferences",
+            get(handlers::notifications::get_preferences)
+                .put(handlers::notifications::update_preferences),
+        )
+        .route(
+            "/notifications/read-all",
+            post(handlers::notifications::mark_all_read),
+        )
+        .route(
+            "/notifications/:id/read",
+            post(handlers::notifications::mark_read),
+        )
         .layer(from_fn(middleware::student_guard))
         .layer(from_fn_with_state(
             state.clone(),
@@ -86,14 +217,51 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let student_routes = student_public.merge(student_protected);
 
+    // Faculty routes
+    let faculty_public = Router::new().route("/login", post(handlers::faculty::login));
+
+    let faculty_protected = Router::new()
+        .route("/me", get(handlers::faculty::get_me))
+        .route("/mentorships", get(handlers::faculty::list_mentorships))
+        .route(
+            "/mentorships/:id",
+            put(handlers::faculty::respond_to_mentorship),
+        )
+        .layer(from_fn_with_state(
+            state.clone(),
+            middleware::faculty_guard,
+        ))
+        .layer(from_fn_with_state(
+            state.clone(),
+            middleware::auth_middleware,
+        ));
+
+    let faculty_routes = faculty_public.merge(faculty_protected);
+
     // Admin routes
     let admin_public = Router::new().route("/login", post(handlers::admin::login));
 
-    let admin_protected = Router::new()
+    // Creating POSTs accept an Idempotency-Key so retries don't duplicate data
+    let admin_writes = Router::new()
+        .route("/hackathons", post(handlers::admin::create_hackathon))
+        .route("/blog", post(handlers::admin::create_blog_post))
+        .route("/faculty", post(handlers::faculty::create_faculty))
+        .route(
+            "/submissions/bulk/status",
+            post(handlers::admin::bulk_update_submission_status),
+        )
         .route(
-            "/hackathons",
-            post(handlers::admin::create_hackathon).get(handlers::admin::list_hackathons_admin),
+            "/submissions/bulk/archive",
+            post(handlers::admin::bulk_archive_submissions),
         )
+        .route(
+            "/submissions/bulk/delete",
+            post(handlers::admin::bulk_delete_submissions),
+        )
+        .layer(from_fn_with_state(state.clone(), middleware::idempotency));
+
+    let admin_protected = Router::new()
+        .route("/hackathons", get(handlers::admin::list_hackathons_admin))
         .route(
             "/hackathons/:id",
             put(handlers::admin::update_hackathon).delete(handlers::admin::delete_hackathon),
@@ -102,6 +270,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             "/hackathons/:id/status",
             patch(handlers::admin::update_hackathon_status),
         )
+        .route(
+            "/hackathons/:id/stats",
+            get(handlers::metrics::get_hackathon_stats),
+        )
+        .route(
+            "/hackathons/:id/certificate-template",
+            get(handlers::certificates::get_certificate_template),
+        )
+        .route(
+            "/hackathons/:id/certificate-template",
+            put(handlers::certificates::upload_certificate_template).layer(DefaultBodyLimit::max(
+                handlers::certificates::TEMPLATE_UPLOAD_BODY_LIMIT,
+            )),
+        )
+        .route(
+            "/hackathons/:id/certificates",
+            get(handlers::certificates::list_certificates)
+                .post(handlers::certificates::issue_certificates),
+        )
+        .route(
+            "/hackathons/:id/certificates/download",
+            get(handlers::certificates::download_certificates),
+        )
         .route("/submissions", get(handlers::admin::list_submissions))
         .route(
             "/submissions/:id",
@@ -111,22 +302,79 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             "/submissions/:id/status",
             patch(handlers::admin::update_submission_status),
         )
-        .route("/blog", post(handlers::admin::create_blog_post))
+        .route(
+            "/submissions/:id/documents/:document_id",
+            get(handlers::admin::download_submission_document),
+        )
+        .route(
+            "/submissions/:id/result",
+            put(handlers::admin::set_submission_result)
+                .delete(handlers::admin::delete_submission_result),
+        )
         .route("/blog/:id", put(handlers::admin::update_blog_post))
         .route("/blog/:id", delete(handlers::admin::delete_blog_post))
         .route("/metrics", get(handlers::metrics::get_metrics))
+        .route("/leaderboard", get(handlers::metrics::get_leaderboard))
+        .route(
+            "/reports/mentors",
+            get(handlers::metrics::get_mentor_report),
+        )
+        .route(
+            "/reports/semester",
+            get(handlers::metrics::get_semester_report),
+        )
+        .route(
+            "/metrics/departments",
+            get(handlers::metrics::get_department_metrics),
+        )
+        .route(
+            "/metrics/timeseries",
+            get(handlers::metrics::get_time_series_metrics),
+        )
+        .route("/events/ticket", post(handlers::events::create_event_ticket))
         .route("/export", get(handlers::metrics::export_data))
+        .route(
+            "/export/presets",
+            get(handlers::export_presets::list_presets)
+                .post(handlers::export_presets::create_preset),
+        )
+        .route(
+            "/export/presets/:id",
+            put(handlers::export_presets::update_preset)
+                .delete(handlers::export_presets::delete_preset),
+        )
+        .route(
+            "/webhooks",
+            post(handlers::webhooks::create_webhook).get(handlers::webhooks::list_webhooks),
+        )
+        .route(
+            "/webhooks/:id",
+            put(handlers::webhooks::update_webhook).delete(handlers::webhooks::delete_webhook),
+        )
+        .route(
+            "/webhooks/:id/deliveries",
+            get(handlers::webhooks::list_deliveries),
+        )
+        .route("/faculty", get(handlers::faculty::list_faculty_admin))
+        .route("/faculty/:id", put(handlers::faculty::update_faculty))
+        .route("/jobs", get(handlers::jobs::list_jobs))
+        .route("/jobs/:id/retry", post(handlers::jobs::retry_job))
+        .merge(admin_writes)
         .layer(from_fn(middleware::admin_guard))
         .layer(from_fn_with_state(
             state.clone(),
             middleware::auth_middleware,
         ));
 
-    let admin_routes = admin_public.merge(admin_protected);
+    // Authenticates itself, since EventSource clients pass a ticket instead of a header
+    let admin_stream = Router::new().route("/events", get(handlers::events::admin_event_stream));
+
+    let admin_routes = admin_public.merge(admin_stream).merge(admin_protected);
 
     let app = Router::new()
         .nest("/api", public_routes)
         .nest("/api/student", student_routes)
+        .nest("/api/faculty", faculty_routes)
         .nest("/api/admin", admin_routes)
         .layer(CorsLayer::permissive())
         .with_state(state);
@@ -134,7 +382,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = std::env::var("BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:8000".to_string());
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     tracing::info!("Server listening on http://{}", addr);
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
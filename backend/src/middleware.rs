@@ -1,21 +1,25 @@
 use crate::auth::verify_jwt;
 use crate::error::AppError;
 use crate::models::Claims;
+use crate::spam::ClientIp;
 use crate::AppState;
 use axum::{
-    extract::{Request, State},
-    http::header::AUTHORIZATION,
+    body::{to_bytes, Body},
+    extract::{OriginalUri, Request, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
     middleware::Next,
     response::Response,
 };
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
 
-pub async fn auth_middleware(
-    State(state): State<AppState>,
-    mut req: Request,
-    next: Next,
-) -> Result<Response, AppError> {
-    let auth_header = req
-        .headers()
+// Claims from a `Bearer` token in the Authorization header
+pub fn bearer_claims(headers: &HeaderMap, secret: &str) -> Result<Claims, AppError> {
+    let auth_header = headers
         .get(AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
         .ok_or_else(|| AppError::Unauthorized("Missing authorization header".to_string()))?;
@@ -24,7 +28,15 @@ pub async fn auth_middleware(
         .strip_prefix("Bearer ")
         .ok_or_else(|| AppError::Unauthorized("Invalid authorization header".to_string()))?;
 
-    let claims = verify_jwt(token, &state.jwt_secret)?;
+    verify_jwt(token, secret)
+}
+
+pub async fn auth_middleware(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let claims = bearer_claims(req.headers(), &state.jwt_secret)?;
 
     // Attach claims to request extensions
     req.extensions_mut().insert(claims);
@@ -57,3 +69,255 @@ pub async fn student_guard(req: Request, next: Next) -> Result<Response, AppErro
 
     Ok(next.run(req).await)
 }
+
+// Deactivating a faculty account also cuts off tokens issued before it
+pub async fn faculty_guard(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .ok_or_else(|| AppError::Unauthorized("Authentication required".to_string()))?;
+
+    if claims.role != "faculty" {
+        return Err(AppError::Forbidden("Faculty access required".to_string()));
+    }
+
+    let faculty_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Unauthorized("Invalid token".to_string()))?;
+    let active: Option<bool> = sqlx::query_scalar("SELECT active FROM faculty WHERE id = $1")
+        .bind(faculty_id)
+        .fetch_optional(&state.db)
+        .await?;
+
+    if active != Some(true) {
+        return Err(AppError::Unauthorized(
+            "Faculty account is no longer active".to_string(),
+        ));
+    }
+
+    Ok(next.run(req).await)
+}
+
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+const IDEMPOTENCY_WINDOW_HOURS: i32 = 24;
+const MAX_IDEMPOTENT_BODY_BYTES: usize = 10 * 1024 * 1024;
+const IDEMPOTENCY_CLEANUP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+// Replays the stored response when a POST is retried with the same
+// Idempotency-Key. Transient failures are not stored, so those requests can be retried.
+pub async fn idempotency(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if req.method() != Method::POST {
+        return Ok(next.run(req).await);
+    }
+
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(req).await);
+    };
+
+    let key = key
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|k| !k.is_empty() && k.len() <= 255)
+        .ok_or_else(|| AppError::BadRequest("Invalid Idempotency-Key header".to_string()))?
+        .to_string();
+
+    // Keys are scoped to the endpoint and, for authenticated routes, the caller
+    let path = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let principal = req
+        .extensions()
+        .get::<Claims>()
+        .map(|claims| claims.sub.clone())
+        .unwrap_or_default();
+    let scope = format!("POST {} {}", path, principal);
+
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, MAX_IDEMPOTENT_BODY_BYTES)
+        .await
+        .map_err(|_| AppError::BadRequest("Request body too large".to_string()))?;
+    let request_hash = hex::encode(Sha256::digest(&body));
+
+    // Expired keys may not have been purged yet; they are claimed like new ones
+    let claimed = sqlx::query(
+        "INSERT INTO idempotency_keys (scope, key, request_hash) VALUES ($1, $2, $3)
+         ON CONFLICT (scope, key) DO UPDATE SET
+            request_hash = EXCLUDED.request_hash,
+            status_code = NULL,
+            content_type = NULL,
+            response_body = NULL,
+            created_at = NOW()
+         WHERE idempotency_keys.created_at < NOW() - $4 * INTERVAL '1 hour'",
+    )
+    .bind(&scope)
+    .bind(&key)
+    .bind(&request_hash)
+    .bind(IDEMPOTENCY_WINDOW_HOURS)
+    .execute(&state.db)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if !claimed {
+        return replay_stored_response(&state, &scope, &key, &request_hash).await;
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            release_key(&state, &scope, &key).await;
+            return Err(AppError::InternalError(format!(
+                "Failed to read response body: {}",
+                e
+            )));
+        }
+    };
+
+    if !is_replayable(parts.status) {
+        release_key(&state, &scope, &key).await;
+    } else {
+        let content_type = parts
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        sqlx::query(
+            "UPDATE idempotency_keys SET status_code = $3, content_type = $4, response_body = $5
+             WHERE scope = $1 AND key = $2",
+        )
+        .bind(&scope)
+        .bind(&key)
+        .bind(parts.status.as_u16() as i32)
+        .bind(content_type)
+        .bind(body.to_vec())
+        .execute(&state.db)
+        .await?;
+    }
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+// Server errors, rate limiting, failed challenges and conflicts are transient,
+// so the client may retry them with the same key
+fn is_replayable(status: StatusCode) -> bool {
+    !(status.is_server_error()
+        || matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::FORBIDDEN | StatusCode::CONFLICT
+        ))
+}
+
+// (request_hash, status_code, content_type, response_body)
+type StoredResponse = (String, Option<i32>, Option<String>, Option<Vec<u8>>);
+
+async fn replay_stored_response(
+    state: &AppState,
+    scope: &str,
+    key: &str,
+    request_hash: &str,
+) -> Result<Response, AppError> {
+    let stored: Option<StoredResponse> = sqlx::query_as(
+        "SELECT request_hash, status_code, content_type, response_body
+         FROM idempotency_keys WHERE scope = $1 AND key = $2",
+    )
+    .bind(scope)
+    .bind(key)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some((stored_hash, status_code, content_type, body)) = stored else {
+        return Err(AppError::Conflict(
+            "A request with this Idempotency-Key is still being processed".to_string(),
+        ));
+    };
+
+    if stored_hash != request_hash {
+        return Err(AppError::Conflict(
+            "Idempotency-Key was already used with a different request".to_string(),
+        ));
+    }
+
+    let Some(status_code) = status_code else {
+        return Err(AppError::Conflict(
+            "A request with this Idempotency-Key is still being processed".to_string(),
+        ));
+    };
+
+    let status = StatusCode::from_u16(status_code as u16)
+        .map_err(|_| AppError::InternalError("Invalid stored status code".to_string()))?;
+
+    let mut response = Response::new(Body::from(body.unwrap_or_default()));
+    *response.status_mut() = status;
+    if let Some(content_type) = content_type.and_then(|ct| HeaderValue::from_str(&ct).ok()) {
+        response.headers_mut().insert(CONTENT_TYPE, content_type);
+    }
+    response
+        .headers_mut()
+        .insert("idempotent-replayed", HeaderValue::from_static("true"));
+
+    Ok(response)
+}
+
+// Purges keys past the replay window. Runs hourly instead of on every request.
+pub fn start_idempotency_cleanup(db: PgPool) {
+    tokio::spawn(async move {
+        loop {
+            let result = sqlx::query(
+                "DELETE FROM idempotency_keys WHERE created_at < NOW() - $1 * INTERVAL '1 hour'",
+            )
+            .bind(IDEMPOTENCY_WINDOW_HOURS)
+            .execute(&db)
+            .await;
+
+            if let Err(e) = result {
+                tracing::error!("Failed to purge expired idempotency keys: {}", e);
+            }
+            tokio::time::sleep(IDEMPOTENCY_CLEANUP_INTERVAL).await;
+        }
+    });
+}
+
+// Frees a key whose request failed so the client can retry it
+async fn release_key(state: &AppState, scope: &str, key: &str) {
+    let result = sqlx::query("DELETE FROM idempotency_keys WHERE scope = $1 AND key = $2")
+        .bind(scope)
+        .bind(key)
+        .execute(&state.db)
+        .await;
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to release idempotency key: {}", e);
+    }
+}
+
+// Per-IP limit for anonymous form submissions
+pub async fn rate_limit(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if let Some(ip) = ip {
+        if !state.rate_limiter.check(ip) {
+            tracing::warn!("Rate limit exceeded for {}", ip);
+            return Err(AppError::TooManyRequests(
+                "Too many submissions, please try again later".to_string(),
+            ));
+        }
+    }
+
+    Ok(next.run(req).await)
+}
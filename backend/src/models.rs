@@ -1,5 +1,7 @@
-use chrono::{DateTime, Utc};
+use crate::filters::SubmissionFilter;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::types::{Json, JsonValue};
 use uuid::Uuid;
 
 // Admin
@@ -49,6 +51,26 @@ pub struct Hackathon {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: Uuid,
+    pub registration_fields: Json<Vec<RegistrationField>>,
+    pub min_team_size: i32,
+    pub max_team_size: i32,
+    pub max_mentors: i32,
+    pub allowed_years: Vec<i32>,       // empty = all years
+    pub allowed_branches: Vec<String>, // empty = all branches
+    pub team_capacity: Option<i32>,    // None = unlimited
+}
+
+// Custom question shown on a hackathon's registration form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationField {
+    pub key: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub field_type: String, // see forms::FIELD_TYPES
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub options: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -65,6 +87,13 @@ pub struct CreateHackathonRequest {
     pub eligibility: String,
     pub semester: String,
     pub status: Option<String>,
+    pub registration_fields: Option<Vec<RegistrationField>>,
+    pub min_team_size: Option<i32>,
+    pub max_team_size: Option<i32>,
+    pub max_mentors: Option<i32>,
+    pub allowed_years: Option<Vec<i32>>,
+    pub allowed_branches: Option<Vec<String>>,
+    pub team_capacity: Option<i32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -80,6 +109,24 @@ pub struct UpdateHackathonRequest {
     pub official_registration_link: Option<String>,
     pub eligibility: Option<String>,
     pub semester: Option<String>,
+    pub registration_fields: Option<Vec<RegistrationField>>,
+    pub min_team_size: Option<i32>,
+    pub max_team_size: Option<i32>,
+    pub max_mentors: Option<i32>,
+    pub allowed_years: Option<Vec<i32>>,
+    pub allowed_branches: Option<Vec<String>>,
+    // Absent leaves the capacity unchanged; `null` removes the limit
+    #[serde(default, deserialize_with = "deserialize_optional_field")]
+    pub team_capacity: Option<Option<i32>>,
+}
+
+// Distinguishes a field set to `null` (Some(None)) from an absent one (None)
+fn deserialize_optional_field<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Deserialize)]
@@ -88,13 +135,40 @@ pub struct UpdateStatusRequest {
 }
 
 // Submission
-#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum SubmissionStatus {
     Submitted,
     Verified,
     Archived,
+    Waitlisted,
+    Withdrawn,
+    Rejected,
+}
+
+impl SubmissionStatus {
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "submitted" => Some(Self::Submitted),
+            "verified" => Some(Self::Verified),
+            "archived" => Some(Self::Archived),
+            "waitlisted" => Some(Self::Waitlisted),
+            "withdrawn" => Some(Self::Withdrawn),
+            "rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submitted => "submitted",
+            Self::Verified => "verified",
+            Self::Archived => "archived",
+            Self::Waitlisted => "waitlisted",
+            Self::Withdrawn => "withdrawn",
+            Self::Rejected => "rejected",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -107,6 +181,20 @@ pub struct Submission {
     pub external_registration_confirmed: bool,
     pub status: String,
     pub created_at: DateTime<Utc>,
+    pub answers: JsonValue,
+    pub waitlisted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WithdrawSubmissionRequest {
+    pub email: String,
+}
+
+// Asks another faculty member to mentor the team after one declined
+#[derive(Debug, Deserialize)]
+pub struct ReplaceMentorRequest {
+    pub email: String,
+    pub faculty_id: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,9 +205,14 @@ pub struct ParticipantInput {
     pub academic_year: String,
 }
 
+// Either a faculty member picked from the directory, or a free-text mentor
+// (name and department) for teams whose mentor has no account
 #[derive(Debug, Deserialize)]
 pub struct MentorInput {
+    pub faculty_id: Option<String>,
+    #[serde(default)]
     pub name: String,
+    #[serde(default)]
     pub department: String,
 }
 
@@ -130,6 +223,43 @@ pub struct SubmitParticipationRequest {
     pub external_registration_confirmed: bool,
     pub participants: Vec<ParticipantInput>,
     pub mentors: Vec<MentorInput>,
+    #[serde(default)]
+    pub answers: serde_json::Map<String, JsonValue>,
+    // Token from the frontend's CAPTCHA widget
+    pub challenge_token: Option<String>,
+    // Honeypot: hidden from people, so only bots fill it in
+    #[serde(default)]
+    pub website: String,
+}
+
+// Bulk submission operations
+#[derive(Debug, Deserialize)]
+pub struct BulkSelection {
+    pub ids: Option<Vec<String>>,
+    pub filter: Option<SubmissionFilter>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkStatusRequest {
+    #[serde(flatten)]
+    pub selection: BulkSelection,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkItemResult {
+    pub id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkOperationResponse {
+    pub processed: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
 }
 
 // Participant
@@ -150,6 +280,137 @@ pub struct Mentor {
     pub submission_id: Uuid,
     pub name: String,
     pub department: String,
+    pub faculty_id: Option<Uuid>,
+    pub status: String,
+    pub response_note: Option<String>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+// Faculty
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Faculty {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    pub department: String,
+    pub designation: Option<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// What teams see when picking a mentor
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct FacultyPublic {
+    pub id: Uuid,
+    pub name: String,
+    pub department: String,
+    pub designation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateFacultyRequest {
+    pub name: String,
+    pub email: String,
+    pub password: String,
+    pub department: String,
+    pub designation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateFacultyRequest {
+    pub name: Option<String>,
+    pub password: Option<String>,
+    pub department: Option<String>,
+    pub designation: Option<String>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FacultyLoginResponse {
+    pub token: String,
+    pub faculty: Faculty,
+}
+
+pub const MENTORSHIP_STATUSES: [&str; 3] = ["pending", "approved", "declined"];
+
+#[derive(Debug, Deserialize)]
+pub struct RespondToMentorshipRequest {
+    pub status: String,
+    pub note: Option<String>,
+}
+
+// A team as seen by the faculty member mentoring it
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Mentorship {
+    pub mentor_id: Uuid,
+    pub status: String,
+    pub response_note: Option<String>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub submission_id: Uuid,
+    pub team_name: String,
+    pub submission_status: String,
+    pub requested_at: DateTime<Utc>,
+    pub hackathon_id: Uuid,
+    pub hackathon_name: String,
+    pub semester: String,
+    #[sqlx(skip)]
+    pub participants: Vec<Participant>,
+}
+
+// Submission document (proof of participation)
+pub const DOCUMENT_KINDS: [&str; 3] = ["registration_proof", "participation_certificate", "other"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SubmissionDocument {
+    pub id: Uuid,
+    pub submission_id: Uuid,
+    pub kind: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub uploaded_by_email: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Submission result (hackathon outcome)
+pub const RESULT_OUTCOMES: [&str; 4] = ["winner", "runner_up", "finalist", "special_mention"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SubmissionResult {
+    pub submission_id: Uuid,
+    pub outcome: String,
+    pub rank: Option<i32>,
+    pub award_title: Option<String>,
+    pub prize_amount: Option<i64>,
+    pub certificate_ref: Option<String>,
+    pub recorded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetSubmissionResultRequest {
+    pub outcome: String,
+    pub rank: Option<i32>,
+    pub award_title: Option<String>,
+    pub prize_amount: Option<i64>,
+    pub certificate_ref: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct HackathonWinner {
+    pub submission_id: Uuid,
+    pub team_name: String,
+    pub outcome: String,
+    pub rank: Option<i32>,
+    pub award_title: Option<String>,
+    pub prize_amount: Option<i64>,
+    pub participants: Vec<String>,
 }
 
 // Blog Post
@@ -217,6 +478,179 @@ pub struct Metrics {
     pub total_mentors: i64,
 }
 
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct YearParticipation {
+    pub academic_year: String,
+    pub unique_students: i64,
+    pub teams: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepartmentParticipation {
+    pub department: String,
+    pub unique_students: i64,
+    pub teams: i64,
+    pub registered_students: i64,
+    pub registered_participants: i64,
+    // Share of the branch's registered students who took part, if any are registered
+    pub participation_rate: Option<f64>,
+    pub years: Vec<YearParticipation>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TimeSeriesBucket {
+    pub period_start: DateTime<Utc>,
+    pub submissions: i64,
+    pub unique_participants: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SemesterComparison {
+    pub semester: String,
+    pub hackathons: i64,
+    pub submissions: i64,
+    pub unique_participants: i64,
+    // Percentage change in submissions from the previous semester
+    #[sqlx(default)]
+    pub submissions_change: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimeSeriesMetrics {
+    pub granularity: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub buckets: Vec<TimeSeriesBucket>,
+    pub semesters: Vec<SemesterComparison>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct LabelCount {
+    pub label: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HackathonStats {
+    pub hackathon_id: Uuid,
+    pub name: String,
+    pub teams: i64,
+    pub teams_by_status: Vec<LabelCount>,
+    pub participants: i64,
+    pub unique_participants: i64,
+    pub mentors: i64,
+    pub average_team_size: Option<f64>,
+    pub team_capacity: Option<i32>,
+    // Verified teams out of all teams that didn't withdraw
+    pub verification_rate: Option<f64>,
+    pub departments: Vec<LabelCount>,
+    pub academic_years: Vec<LabelCount>,
+    pub outcomes: Vec<LabelCount>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicHackathonStats {
+    pub hackathon_id: Uuid,
+    pub teams: i64,
+    pub departments: Vec<String>,
+}
+
+// Certificates
+pub const CERTIFICATE_PLACEHOLDERS: [&str; 5] =
+    ["{name}", "{team}", "{hackathon}", "{award}", "{date}"];
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CertificateTemplate {
+    pub hackathon_id: Uuid,
+    pub title: String,
+    pub body: String,
+    pub signatory_name: Option<String>,
+    pub signatory_title: Option<String>,
+    #[serde(skip_serializing)]
+    pub background_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Certificate {
+    pub id: Uuid,
+    pub verification_code: String,
+    pub participant_id: Uuid,
+    pub submission_id: Uuid,
+    pub hackathon_id: Uuid,
+    pub participant_name: String,
+    pub participant_email: String,
+    pub team_name: String,
+    pub hackathon_name: String,
+    pub award: String,
+    pub issued_at: DateTime<Utc>,
+    // Set while the team is rejected or withdrawn
+    pub revoked_at: Option<DateTime<Utc>>,
+    // Last time the award changed after issue
+    pub reissued_at: Option<DateTime<Utc>>,
+}
+
+// Public answer to "is this certificate genuine?"
+#[derive(Debug, Serialize)]
+pub struct CertificateVerification {
+    pub valid: bool,
+    pub verification_code: String,
+    pub participant_name: String,
+    pub team_name: String,
+    pub hackathon_name: String,
+    pub award: String,
+    pub issued_at: DateTime<Utc>,
+    pub reissued_at: Option<DateTime<Utc>>,
+}
+
+impl CertificateVerification {
+    pub fn new(certificate: Certificate, valid: bool) -> Self {
+        Self {
+            valid,
+            verification_code: certificate.verification_code,
+            participant_name: certificate.participant_name,
+            team_name: certificate.team_name,
+            hackathon_name: certificate.hackathon_name,
+            award: certificate.award,
+            issued_at: certificate.issued_at,
+            reissued_at: certificate.reissued_at,
+        }
+    }
+}
+
+// Mentor workload report
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct MentorWorkload {
+    pub name: String,
+    pub department: String,
+    pub semester: String,
+    pub teams: i64,
+    pub hackathons: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DepartmentMentorCount {
+    pub department: String,
+    pub semester: String,
+    pub mentors: i64,
+    pub teams: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MentorReport {
+    pub semester: Option<String>,
+    pub mentors: Vec<MentorWorkload>,
+    pub departments: Vec<DepartmentMentorCount>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepartmentMetrics {
+    pub semester: Option<String>,
+    pub departments: Vec<DepartmentParticipation>,
+    pub years: Vec<YearParticipation>,
+}
+
 // Auth
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
@@ -242,8 +676,20 @@ pub struct AdminResponse {
 pub struct Claims {
     pub sub: String,
     pub email: String,
-    pub role: String, // "admin" or "student"
+    pub role: String, // "admin", "student" or "faculty"
+    pub exp: i64,
+}
+
+// Short-lived ticket for opening the admin event stream. Browsers' EventSource
+// can't send an Authorization header, so the ticket goes in the query string;
+// it can't be used as a session token anywhere else.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreamTicketClaims {
+    pub sub: String,
+    pub purpose: String,
     pub exp: i64,
+    // The stream closes when the admin session that issued the ticket expires
+    pub session_exp: i64,
 }
 
 // Submission with relations (for export/view)
@@ -251,7 +697,6 @@ pub struct Claims {
 pub struct SubmissionDetail {
     pub submission_id: Uuid,
     pub submitted_at: DateTime<Utc>,
-    #[allow(dead_code)]
     pub semester: String,
     pub hackathon_name: String,
     pub team_name: String,
@@ -265,6 +710,41 @@ pub struct SubmissionDetail {
     pub mentor_departments: Option<String>,
     pub external_confirmed: bool,
     pub status: String,
+    pub result_outcome: Option<String>,
+    pub result_rank: Option<i32>,
+    pub award_title: Option<String>,
+    pub prize_amount: Option<i64>,
+    pub certificate_ref: Option<String>,
+    pub answers: JsonValue,
+}
+
+// Saved export settings, private to the admin who created them
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportPreset {
+    pub id: Uuid,
+    pub admin_id: Uuid,
+    pub name: String,
+    pub columns: Vec<String>,
+    pub layout: String,
+    pub format: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateExportPresetRequest {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub layout: Option<String>,
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateExportPresetRequest {
+    pub name: Option<String>,
+    pub columns: Option<Vec<String>>,
+    pub layout: Option<String>,
+    pub format: Option<String>,
 }
 
 // Student
@@ -322,3 +802,130 @@ pub struct StudentSearchRequest {
     pub branch: Option<String>,
     pub skills: Option<String>,
 }
+
+// Background Job
+pub const JOB_STATUSES: [&str; 4] = ["pending", "running", "completed", "dead"];
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Job {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: JsonValue,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub dedupe_key: Option<String>,
+}
+
+// Notification
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    pub student_id: Uuid,
+    pub category: String,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub const NOTIFICATION_MODES: [&str; 3] = ["instant", "digest", "off"];
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct NotificationPreferences {
+    pub submission: String,
+    pub hackathon: String,
+    pub blog: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub submission: Option<String>,
+    pub hackathon: Option<String>,
+    pub blog: Option<String>,
+}
+
+// Webhook
+pub const WEBHOOK_EVENTS: [&str; 4] = [
+    "hackathon.created",
+    "submission.created",
+    "submission.status_changed",
+    "blog.published",
+];
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub events: Vec<String>,
+    pub description: Option<String>,
+    pub active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub events: Vec<String>,
+    pub description: Option<String>,
+    // Generated when omitted
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event: String,
+    pub payload: JsonValue,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+// Leaderboard
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub student_id: Uuid,
+    pub name: String,
+    pub branch: String,
+    pub year: i32,
+    pub verified_participations: i64,
+    pub wins: i64,
+    pub awards: i64,
+    // Only included for admins
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opted_out: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardOptOutRequest {
+    pub opt_out: bool,
+}
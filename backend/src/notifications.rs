@@ -0,0 +1,241 @@
+use crate::error::AppResult;
+use crate::jobs;
+use crate::mailer::Email;
+use crate::models::{BlogPost, Hackathon, Mentor, Submission};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+// Notifications are written on the caller's connection so they commit (or
+// roll back) together with the change they describe. Each category maps to a
+// preference column on `students`, which only decides the email: `instant`
+// queues one, `digest` leaves it to the weekly digest and `off` sends none.
+// The inbox always gets the notification.
+
+// Students follow their team's submissions from the dashboard
+const TEAM_LINK: &str = "/student/dashboard";
+
+// Payload of a notification email job. The link is relative to the frontend
+// and is made absolute when the job runs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationEmail {
+    pub to: String,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+}
+
+impl NotificationEmail {
+    pub fn into_email(self, frontend_url: &str) -> Email {
+        let mut body = self.body;
+        if let Some(link) = self.link {
+            body.push_str(&format!(
+                "\n\n{}{}",
+                frontend_url.trim_end_matches('/'),
+                link
+            ));
+        }
+
+        Email {
+            to: self.to,
+            subject: self.title,
+            body: format!("{}\n\nCOIN SREC\n", body),
+        }
+    }
+}
+
+fn preference_column(category: &str) -> &'static str {
+    match category {
+        "submission" => "notify_submission",
+        "hackathon" => "notify_hackathon",
+        _ => "notify_blog",
+    }
+}
+
+enum Audience {
+    // Registered students whose email is on the submission's team
+    Team(Uuid),
+    AllStudents,
+}
+
+// Writes the notification for every recipient, and queues emails for those
+// who want them instantly
+async fn notify(
+    conn: &mut PgConnection,
+    category: &str,
+    audience: Audience,
+    title: String,
+    body: String,
+    link: String,
+) -> AppResult<()> {
+    let column = preference_column(category);
+    let mut qb = QueryBuilder::<Postgres>::new(format!(
+        "SELECT id, email, {} FROM students WHERE 1=1",
+        column
+    ));
+    if let Audience::Team(submission_id) = audience {
+        qb.push(
+            " AND LOWER(email) IN (SELECT LOWER(email) FROM participants WHERE submission_id = ",
+        )
+        .push_bind(submission_id)
+        .push(")");
+    }
+
+    let recipients: Vec<(Uuid, String, String)> = qb.build_query_as().fetch_all(&mut *conn).await?;
+
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let student_ids: Vec<Uuid> = recipients.iter().map(|(id, _, _)| *id).collect();
+    sqlx::query(
+        "INSERT INTO notifications (student_id, category, title, body, link)
+         SELECT student_id, $2, $3, $4, $5 FROM UNNEST($1::uuid[]) AS student_id",
+    )
+    .bind(&student_ids)
+    .bind(category)
+    .bind(&title)
+    .bind(&body)
+    .bind(&link)
+    .execute(&mut *conn)
+    .await?;
+
+    for (_, email, mode) in recipients {
+        if mode == "instant" {
+            let payload = NotificationEmail {
+                to: email,
+                title: title.clone(),
+                body: body.clone(),
+                link: Some(link.clone()),
+            };
+            jobs::enqueue(conn, jobs::NOTIFICATION_EMAIL, &payload).await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Tells every registered student on the team about a status change
+pub async fn submission_status_changed(
+    conn: &mut PgConnection,
+    submission: &Submission,
+) -> AppResult<()> {
+    let hackathon_name: String = sqlx::query_scalar("SELECT name FROM hackathons WHERE id = $1")
+        .bind(submission.hackathon_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    notify(
+        conn,
+        "submission",
+        Audience::Team(submission.id),
+        format!(
+            "Team {} is now {}",
+            submission.team_name,
+            submission.status.replace('_', " ")
+        ),
+        format!(
+            "Your submission for {} was marked as {}.",
+            hackathon_name, submission.status
+        ),
+        TEAM_LINK.to_string(),
+    )
+    .await
+}
+
+// Faculty don't have in-app notifications, so a mentorship request is emailed
+pub async fn mentorship_requested(conn: &mut PgConnection, mentor: &Mentor) -> AppResult<()> {
+    let Some(faculty_id) = mentor.faculty_id else {
+        return Ok(());
+    };
+
+    let (email, team_name, hackathon_name): (String, String, String) = sqlx::query_as(
+        "SELECT f.email, s.team_name, h.name
+         FROM submissions s
+         JOIN hackathons h ON s.hackathon_id = h.id
+         JOIN faculty f ON f.id = $1
+         WHERE s.id = $2",
+    )
+    .bind(faculty_id)
+    .bind(mentor.submission_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let payload = NotificationEmail {
+        to: email,
+        title: format!("Mentorship request from team {}", team_name),
+        body: format!(
+            "Hi {},\n\nTeam {} has asked you to mentor them for {}. \
+             Please approve or decline the request.",
+            mentor.name, team_name, hackathon_name
+        ),
+        link: Some("/faculty/mentorships".to_string()),
+    };
+    jobs::enqueue(conn, jobs::NOTIFICATION_EMAIL, &payload).await?;
+
+    Ok(())
+}
+
+// Tells the team whether their chosen mentor accepted
+pub async fn mentorship_responded(conn: &mut PgConnection, mentor: &Mentor) -> AppResult<()> {
+    let team_name: String = sqlx::query_scalar("SELECT team_name FROM submissions WHERE id = $1")
+        .bind(mentor.submission_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let mut body = format!(
+        "{} has {} the request to mentor team {}.",
+        mentor.name, mentor.status, team_name
+    );
+    if let Some(note) = &mentor.response_note {
+        body.push_str(&format!(" Note: {}", note));
+    }
+    if mentor.status == "declined" {
+        body.push_str(" You can ask another faculty member to mentor the team instead.");
+    }
+
+    notify(
+        conn,
+        "submission",
+        Audience::Team(mentor.submission_id),
+        format!("Mentorship {}: {}", mentor.status, mentor.name),
+        body,
+        TEAM_LINK.to_string(),
+    )
+    .await
+}
+
+pub async fn hackathon_created(conn: &mut PgConnection, hackathon: &Hackathon) -> AppResult<()> {
+    notify(
+        conn,
+        "hackathon",
+        Audience::AllStudents,
+        format!("New hackathon: {}", hackathon.name),
+        format!(
+            "{} by {}. Registration closes on {}.",
+            hackathon.name,
+            hackathon.organizer,
+            hackathon.registration_deadline.format("%d %b %Y")
+        ),
+        format!(
+            "/hackathons/{}",
+            hackathon
+                .slug
+                .clone()
+                .unwrap_or_else(|| hackathon.id.to_string())
+        ),
+    )
+    .await
+}
+
+pub async fn blog_post_published(conn: &mut PgConnection, post: &BlogPost) -> AppResult<()> {
+    notify(
+        conn,
+        "blog",
+        Audience::AllStudents,
+        post.title.clone(),
+        post.summary.clone(),
+        format!("/blog/{}", post.slug),
+    )
+    .await
+}
@@ -0,0 +1,897 @@
+use crate::error::{AppError, AppResult};
+use crate::models::SubmissionDetail;
+use crate::utils::academic_year_number;
+use chrono::Utc;
+use printpdf::image_crate::{self, codecs::jpeg::JpegEncoder, imageops::FilterType, Rgb, RgbImage};
+use printpdf::{
+    BuiltinFont, ColorBits, ColorSpace, Image, ImageFilter, ImageTransform, ImageXObject,
+    IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point, Px,
+};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+// A4 portrait, in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 18.0;
+const BODY_SIZE: f32 = 9.0;
+
+// Teams that dropped out or were turned away didn't take part
+const EXCLUDED_STATUSES: [&str; 2] = ["withdrawn", "rejected"];
+
+fn pdf_error(e: printpdf::Error) -> AppError {
+    AppError::InternalError(format!("PDF error: {}", e))
+}
+
+// Line height in mm for a font size in points
+fn line_height(size: f32) -> f32 {
+    size * 0.3528 * 1.45
+}
+
+// Cuts text to roughly fit a column. Helvetica averages about half an em per
+// character, which is close enough for names and labels.
+fn fit(text: &str, width: f32, size: f32) -> String {
+    let max_chars = (width / (size * 0.3528 * 0.52)) as usize;
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", cut.trim_end())
+}
+
+// Simple top-to-bottom page writer on top of printpdf: keeps track of the
+// current position and starts a new page when the next block doesn't fit
+struct Writer {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    page: usize,
+    y: f32,
+}
+
+impl Writer {
+    fn new(title: &str) -> AppResult<Self> {
+        let (doc, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(pdf_error)?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(pdf_error)?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        let writer = Self {
+            doc,
+            layer,
+            regular,
+            bold,
+            page: 1,
+            y: PAGE_HEIGHT - MARGIN,
+        };
+        writer.footer();
+        Ok(writer)
+    }
+
+    fn footer(&self) {
+        self.layer.use_text(
+            format!("Page {}", self.page),
+            8.0,
+            Mm(PAGE_WIDTH - MARGIN - 12.0),
+            Mm(MARGIN / 2.0),
+            &self.regular,
+        );
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self
+            .doc
+            .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.page += 1;
+        self.y = PAGE_HEIGHT - MARGIN;
+        self.footer();
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.new_page();
+        }
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn text_at(&self, text: &str, size: f32, x: f32, bold: bool) {
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer.use_text(text, size, Mm(x), Mm(self.y), font);
+    }
+
+    fn line(&mut self, text: &str, size: f32, bold: bool) {
+        self.ensure_space(line_height(size));
+        self.y -= line_height(size);
+        self.text_at(
+            &fit(text, PAGE_WIDTH - 2.0 * MARGIN, size),
+            size,
+            MARGIN,
+            bold,
+        );
+    }
+
+    fn heading(&mut self, text: &str) {
+        // Keep a heading on the same page as the first lines under it
+        self.ensure_space(line_height(13.0) + 4.0 * line_height(BODY_SIZE));
+        self.space(3.0);
+        self.line(text, 13.0, true);
+        self.space(1.0);
+    }
+
+    fn rule(&self) {
+        let y = self.y - 1.2;
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(y)), false),
+                (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(y)), false),
+            ],
+            is_closed: false,
+        });
+    }
+
+    fn row(&mut self, widths: &[f32], cells: &[String], bold: bool) {
+        self.y -= line_height(BODY_SIZE);
+        let mut x = MARGIN;
+        for (cell, width) in cells.iter().zip(widths) {
+            self.text_at(&fit(cell, width - 2.0, BODY_SIZE), BODY_SIZE, x, bold);
+            x += width;
+        }
+    }
+
+    // Column widths are in mm; the header is repeated on every page the table spans
+    fn table(&mut self, columns: &[(&str, f32)], rows: &[Vec<String>]) {
+        let widths: Vec<f32> = columns.iter().map(|(_, width)| *width).collect();
+        let header: Vec<String> = columns.iter().map(|(name, _)| name.to_string()).collect();
+
+        self.ensure_space(2.0 * line_height(BODY_SIZE));
+        self.row(&widths, &header, true);
+        self.rule();
+        self.space(1.0);
+
+        for cells in rows {
+            if self.y - line_height(BODY_SIZE) < MARGIN {
+                self.new_page();
+                self.row(&widths, &header, true);
+                self.rule();
+                self.space(1.0);
+            }
+            self.row(&widths, cells, false);
+        }
+        self.space(2.0);
+    }
+
+    fn finish(self) -> AppResult<Vec<u8>> {
+        self.doc.save_to_bytes().map_err(pdf_error)
+    }
+}
+
+struct Member {
+    name: String,
+    email: String,
+    department: String,
+    year: String,
+}
+
+struct Team {
+    name: String,
+    status: String,
+    mentors: String,
+    outcome: Option<String>,
+    rank: Option<i32>,
+    award_title: Option<String>,
+    prize_amount: Option<i64>,
+    members: Vec<Member>,
+}
+
+// Export rows are one per participant; this folds them back into teams,
+// grouped by hackathon and sorted by name
+fn group_teams(rows: &[SubmissionDetail]) -> BTreeMap<String, Vec<Team>> {
+    let mut teams: BTreeMap<(String, String, Uuid), Team> = BTreeMap::new();
+
+    for row in rows {
+        if EXCLUDED_STATUSES.contains(&row.status.as_str()) {
+            continue;
+        }
+
+        let key = (
+            row.hackathon_name.clone(),
+            row.team_name.to_lowercase(),
+            row.submission_id,
+        );
+        let team = teams.entry(key).or_insert_with(|| Team {
+            name: row.team_name.clone(),
+            status: row.status.clone(),
+            mentors: row.mentor_names.clone().unwrap_or_default(),
+            outcome: row.result_outcome.clone(),
+            rank: row.result_rank,
+            award_title: row.award_title.clone(),
+            prize_amount: row.prize_amount,
+            members: Vec::new(),
+        });
+
+        if let Some(name) = &row.participant_name {
+            team.members.push(Member {
+                name: name.clone(),
+                email: row.participant_email.clone().unwrap_or_default(),
+                department: row.participant_department.clone().unwrap_or_default(),
+                year: row.participant_year.clone().unwrap_or_default(),
+            });
+        }
+    }
+
+    let mut hackathons: BTreeMap<String, Vec<Team>> = BTreeMap::new();
+    for ((hackathon, _, _), mut team) in teams {
+        team.members.sort_by(|a, b| a.name.cmp(&b.name));
+        hackathons.entry(hackathon).or_default().push(team);
+    }
+    hackathons
+}
+
+fn count_rows(counts: BTreeMap<String, (i64, HashSet<Uuid>)>) -> Vec<Vec<String>> {
+    let mut counts: Vec<(String, i64, usize)> = counts
+        .into_iter()
+        .map(|(label, (participants, teams))| (label, participants, teams.len()))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+        .into_iter()
+        .map(|(label, participants, teams)| {
+            vec![label, participants.to_string(), teams.to_string()]
+        })
+        .collect()
+}
+
+// Participant rows that count towards the report
+fn participations(rows: &[SubmissionDetail]) -> impl Iterator<Item = &SubmissionDetail> {
+    rows.iter().filter(|row| {
+        !EXCLUDED_STATUSES.contains(&row.status.as_str()) && row.participant_name.is_some()
+    })
+}
+
+// Departments are grouped ignoring case and surrounding spaces, like the
+// analytics endpoints, and labelled with the alphabetically first spelling
+fn department_rows(rows: &[SubmissionDetail]) -> Vec<Vec<String>> {
+    let mut by_department: BTreeMap<String, (String, i64, HashSet<Uuid>)> = BTreeMap::new();
+    for row in participations(rows) {
+        let department = row
+            .participant_department
+            .as_deref()
+            .unwrap_or_default()
+            .trim();
+        let entry = by_department
+            .entry(department.to_lowercase())
+            .or_insert_with(|| (department.to_string(), 0, HashSet::new()));
+        if department < entry.0.as_str() {
+            entry.0 = department.to_string();
+        }
+        entry.1 += 1;
+        entry.2.insert(row.submission_id);
+    }
+
+    count_rows(
+        by_department
+            .into_values()
+            .map(|(label, participants, teams)| (label, (participants, teams)))
+            .collect(),
+    )
+}
+
+fn year_rows(rows: &[SubmissionDetail]) -> Vec<Vec<String>> {
+    let mut by_year: BTreeMap<String, (i64, HashSet<Uuid>)> = BTreeMap::new();
+    for row in participations(rows) {
+        let year = row.participant_year.clone().unwrap_or_default();
+        let entry = by_year.entry(year).or_default();
+        entry.0 += 1;
+        entry.1.insert(row.submission_id);
+    }
+
+    let mut year_rows = count_rows(by_year);
+    year_rows.sort_by_key(|row| academic_year_number(&row[0]).unwrap_or(i32::MAX));
+    year_rows
+}
+
+fn summary_rows(hackathons: &BTreeMap<String, Vec<Team>>) -> Vec<Vec<String>> {
+    let teams: Vec<&Team> = hackathons.values().flatten().collect();
+    let members: Vec<&Member> = teams.iter().flat_map(|t| &t.members).collect();
+
+    let unique_students: HashSet<String> = members.iter().map(|m| m.email.to_lowercase()).collect();
+    let mentors: HashSet<String> = teams
+        .iter()
+        .flat_map(|t| t.mentors.split(", "))
+        .filter(|m| !m.is_empty())
+        .map(|m| m.trim().to_lowercase())
+        .collect();
+    let winners = teams
+        .iter()
+        .filter(|t| t.outcome.as_deref() == Some("winner"))
+        .count();
+    let awarded = teams.iter().filter(|t| t.outcome.is_some()).count();
+
+    vec![
+        vec!["Hackathons".to_string(), hackathons.len().to_string()],
+        vec!["Teams".to_string(), teams.len().to_string()],
+        vec!["Participations".to_string(), members.len().to_string()],
+        vec![
+            "Unique students".to_string(),
+            unique_students.len().to_string(),
+        ],
+        vec!["Faculty mentors".to_string(), mentors.len().to_string()],
+        vec!["Teams with a result".to_string(), awarded.to_string()],
+        vec!["Winning teams".to_string(), winners.to_string()],
+    ]
+}
+
+// Semester summary for accreditation paperwork: headline counts, department
+// and year tables, results and the roster of every hackathon
+pub fn generate_semester_report(semester: &str, rows: &[SubmissionDetail]) -> AppResult<Vec<u8>> {
+    let hackathons = group_teams(rows);
+
+    let mut w = Writer::new(&format!("Hackathon Participation Report - {}", semester))?;
+
+    w.line("Hackathon Participation Report", 18.0, true);
+    w.space(1.0);
+    w.line(&format!("Semester: {}", semester), 11.0, false);
+    w.line(
+        &format!("Generated on {} UTC", Utc::now().format("%d %b %Y %H:%M")),
+        BODY_SIZE,
+        false,
+    );
+    w.line(
+        "Withdrawn and rejected registrations are not included.",
+        BODY_SIZE,
+        false,
+    );
+
+    w.heading("Summary");
+    w.table(
+        &[("Measure", 90.0), ("Count", 40.0)],
+        &summary_rows(&hackathons),
+    );
+
+    w.heading("Participation by department");
+    w.table(
+        &[
+            ("Department", 110.0),
+            ("Participants", 32.0),
+            ("Teams", 32.0),
+        ],
+        &department_rows(rows),
+    );
+
+    w.heading("Participation by academic year");
+    w.table(
+        &[
+            ("Academic year", 110.0),
+            ("Participants", 32.0),
+            ("Teams", 32.0),
+        ],
+        &year_rows(rows),
+    );
+
+    w.heading("Winners and awards");
+    let mut results: Vec<(&String, &Team)> = hackathons
+        .iter()
+        .flat_map(|(hackathon, teams)| teams.iter().map(move |team| (hackathon, team)))
+        .filter(|(_, team)| team.outcome.is_some())
+        .collect();
+    results.sort_by_key(|(hackathon, team)| (*hackathon, team.rank.unwrap_or(i32::MAX)));
+    if results.is_empty() {
+        w.line("No results have been recorded.", BODY_SIZE, false);
+    } else {
+        let rows: Vec<Vec<String>> = results
+            .iter()
+            .map(|(hackathon, team)| {
+                vec![
+                    hackathon.to_string(),
+                    team.name.clone(),
+                    team.outcome.clone().unwrap_or_default().replace('_', " "),
+                    team.rank.map(|r| r.to_string()).unwrap_or_default(),
+                    team.award_title.clone().unwrap_or_default(),
+                    team.prize_amount.map(|p| p.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        w.table(
+            &[
+                ("Hackathon", 46.0),
+                ("Team", 36.0),
+                ("Outcome", 24.0),
+                ("Rank", 12.0),
+                ("Award", 34.0),
+                ("Prize", 22.0),
+            ],
+            &rows,
+        );
+    }
+
+    for (hackathon, teams) in &hackathons {
+        w.new_page();
+        w.line(hackathon, 14.0, true);
+        w.line(&format!("{} teams", teams.len()), BODY_SIZE, false);
+
+        for team in teams {
+            w.ensure_space(4.0 * line_height(BODY_SIZE));
+            w.space(2.0);
+            w.line(
+                &format!("{} ({})", team.name, team.status.replace('_', " ")),
+                10.0,
+                true,
+            );
+            if !team.mentors.is_empty() {
+                w.line(&format!("Mentors: {}", team.mentors), BODY_SIZE, false);
+            }
+            let rows: Vec<Vec<String>> = team
+                .members
+                .iter()
+                .map(|m| {
+                    vec![
+                        m.name.clone(),
+                        m.department.clone(),
+                        m.year.clone(),
+                        m.email.clone(),
+                    ]
+                })
+                .collect();
+            w.table(
+                &[
+                    ("Name", 48.0),
+                    ("Department", 50.0),
+                    ("Year", 20.0),
+                    ("Email", 56.0),
+                ],
+                &rows,
+            );
+        }
+    }
+
+    w.finish()
+}
+
+// Certificates are A4 landscape
+const CERTIFICATE_WIDTH: f32 = 297.0;
+const CERTIFICATE_HEIGHT: f32 = 210.0;
+// Backgrounds are scaled down to 150 dpi across the page, which prints fine
+// and keeps a ZIP of a few hundred certificates manageable
+const MAX_BACKGROUND_WIDTH_PX: u32 = 1754;
+
+// Background image re-encoded as JPEG, so it can be embedded compressed in
+// any number of certificates without decoding it again
+pub struct Background {
+    width: u32,
+    height: u32,
+    jpeg: Vec<u8>,
+}
+
+pub fn decode_background(data: &[u8]) -> AppResult<Background> {
+    let mut image = image_crate::load_from_memory(data)
+        .map_err(|e| AppError::BadRequest(format!("Invalid background image: {}", e)))?;
+
+    if image.width() > MAX_BACKGROUND_WIDTH_PX {
+        let height = image.height() * MAX_BACKGROUND_WIDTH_PX / image.width();
+        image = image.resize(MAX_BACKGROUND_WIDTH_PX, height, FilterType::Triangle);
+    }
+
+    // JPEG has no alpha channel, so transparent areas are flattened onto white
+    let rgba = image.to_rgba8();
+    let rgb = RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let pixel = rgba.get_pixel(x, y);
+        let alpha = pixel[3] as u32;
+        Rgb([0, 1, 2].map(|i| ((pixel[i] as u32 * alpha + 255 * (255 - alpha)) / 255) as u8))
+    });
+
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, 85)
+        .encode_image(&rgb)
+        .map_err(|e| AppError::InternalError(format!("Image encoding failed: {}", e)))?;
+
+    Ok(Background {
+        width: rgb.width(),
+        height: rgb.height(),
+        jpeg,
+    })
+}
+
+// Rough Helvetica text width in mm, used to centre lines
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.3528 * 0.5
+}
+
+fn wrap(text: &str, width: f32, size: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if !line.is_empty() && text_width(&candidate, size) > width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+pub struct CertificateContent<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub signatory_name: Option<&'a str>,
+    pub signatory_title: Option<&'a str>,
+    pub verification: &'a str,
+}
+
+// One certificate page: optional full-page background, then the title, body,
+// signature block and verification line centred on top of it
+pub fn generate_certificate(
+    content: &CertificateContent,
+    background: Option<&Background>,
+) -> AppResult<Vec<u8>> {
+    let (doc, page, layer) = PdfDocument::new(
+        content.title,
+        Mm(CERTIFICATE_WIDTH),
+        Mm(CERTIFICATE_HEIGHT),
+        "Layer 1",
+    );
+    let regular = doc
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(pdf_error)?;
+    let bold = doc
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(pdf_error)?;
+    let layer = doc.get_page(page).get_layer(layer);
+
+    if let Some(background) = background {
+        // Stretch the image over the whole page
+        let dpi = background.width as f32 / (CERTIFICATE_WIDTH / 25.4);
+        let natural_height = background.height as f32 / dpi * 25.4;
+        let image = ImageXObject {
+            width: Px(background.width as usize),
+            height: Px(background.height as usize),
+            color_space: ColorSpace::Rgb,
+            bits_per_component: ColorBits::Bit8,
+            interpolate: true,
+            image_data: background.jpeg.clone(),
+            image_filter: Some(ImageFilter::DCT),
+            smask: None,
+            clipping_bbox: None,
+        };
+        Image::from(image).add_to_layer(
+            layer.clone(),
+            ImageTransform {
+                dpi: Some(dpi),
+                scale_y: Some(CERTIFICATE_HEIGHT / natural_height),
+                ..Default::default()
+            },
+        );
+    }
+
+    let centred = |text: &str, size: f32, y: f32, font: &IndirectFontRef| {
+        let x = ((CERTIFICATE_WIDTH - text_width(text, size)) / 2.0).max(10.0);
+        layer.use_text(text, size, Mm(x), Mm(y), font);
+    };
+
+    centred(content.title, 30.0, 150.0, &bold);
+
+    let mut y = 128.0;
+    for line in wrap(content.body, CERTIFICATE_WIDTH - 70.0, 14.0) {
+        centred(&line, 14.0, y, &regular);
+        y -= line_height(14.0);
+    }
+
+    if let Some(name) = content.signatory_name {
+        centred("______________________________", 11.0, 52.0, &regular);
+        centred(name, 11.0, 45.0, &bold);
+        if let Some(title) = content.signatory_title {
+            centred(title, 10.0, 40.0, &regular);
+        }
+    }
+
+    centred(content.verification, 8.0, 14.0, &regular);
+
+    doc.save_to_bytes().map_err(pdf_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One export row per participant, like `fetch_submission_details` returns
+    fn row(
+        submission_id: Uuid,
+        hackathon: &str,
+        team: &str,
+        status: &str,
+        participant: Option<(&str, &str, &str)>,
+    ) -> SubmissionDetail {
+        let name = participant.map(|p| p.0);
+        SubmissionDetail {
+            submission_id,
+            submitted_at: Utc::now(),
+            semester: "2024-odd".to_string(),
+            hackathon_name: hackathon.to_string(),
+            team_name: team.to_string(),
+            participant_count: 1,
+            mentor_count: 1,
+            participant_name: name.map(str::to_string),
+            participant_email: name.map(|n| format!("{}@srec.ac.in", n.to_lowercase())),
+            participant_department: participant.map(|p| p.1.to_string()),
+            participant_year: participant.map(|p| p.2.to_string()),
+            mentor_names: Some("Dr. Rao".to_string()),
+            mentor_departments: Some("CSE".to_string()),
+            external_confirmed: false,
+            status: status.to_string(),
+            result_outcome: None,
+            result_rank: None,
+            award_title: None,
+            prize_amount: None,
+            certificate_ref: None,
+            answers: serde_json::json!({}),
+        }
+    }
+
+    fn with_result(mut row: SubmissionDetail, outcome: &str, rank: i32) -> SubmissionDetail {
+        row.result_outcome = Some(outcome.to_string());
+        row.result_rank = Some(rank);
+        row
+    }
+
+    fn count(rows: &[Vec<String>], label: &str) -> Vec<String> {
+        rows.iter()
+            .find(|row| row[0] == label)
+            .unwrap_or_else(|| panic!("no row for {}", label))[1..]
+            .to_vec()
+    }
+
+    #[test]
+    fn group_teams_folds_participants_into_teams_per_hackathon() {
+        let (alpha, beta, gamma) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![
+            row(beta, "SIH", "beta", "verified", Some(("Zoe", "CSE", "2"))),
+            row(alpha, "SIH", "Alpha", "verified", Some(("Ravi", "IT", "3"))),
+            row(alpha, "SIH", "Alpha", "verified", Some(("Anu", "IT", "3"))),
+            row(gamma, "Codeathon", "Gamma", "pending", None),
+        ];
+
+        let hackathons = group_teams(&rows);
+
+        assert_eq!(hackathons.keys().collect::<Vec<_>>(), ["Codeathon", "SIH"]);
+        let sih: Vec<&str> = hackathons["SIH"].iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(sih, ["Alpha", "beta"]);
+        let members: Vec<&str> = hackathons["SIH"][0]
+            .members
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(members, ["Anu", "Ravi"]);
+        assert!(hackathons["Codeathon"][0].members.is_empty());
+    }
+
+    #[test]
+    fn group_teams_keeps_same_named_teams_apart() {
+        let rows = vec![
+            row(
+                Uuid::new_v4(),
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Anu", "IT", "3")),
+            ),
+            row(
+                Uuid::new_v4(),
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Ravi", "IT", "3")),
+            ),
+        ];
+
+        assert_eq!(group_teams(&rows)["SIH"].len(), 2);
+    }
+
+    #[test]
+    fn withdrawn_and_rejected_teams_are_left_out() {
+        let rows = vec![
+            row(
+                Uuid::new_v4(),
+                "SIH",
+                "Kept",
+                "verified",
+                Some(("Anu", "IT", "3")),
+            ),
+            row(
+                Uuid::new_v4(),
+                "SIH",
+                "Gone",
+                "withdrawn",
+                Some(("Ravi", "IT", "3")),
+            ),
+            row(
+                Uuid::new_v4(),
+                "Codeathon",
+                "Out",
+                "rejected",
+                Some(("Zoe", "ECE", "1")),
+            ),
+        ];
+
+        let hackathons = group_teams(&rows);
+        assert_eq!(hackathons.len(), 1);
+        assert_eq!(hackathons["SIH"].len(), 1);
+        assert_eq!(department_rows(&rows), [["IT", "1", "1"]]);
+        assert_eq!(year_rows(&rows), [["3", "1", "1"]]);
+    }
+
+    #[test]
+    fn summary_counts_teams_students_mentors_and_results() {
+        let (alpha, beta, gamma) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![
+            with_result(
+                row(alpha, "SIH", "Alpha", "verified", Some(("Anu", "IT", "3"))),
+                "winner",
+                1,
+            ),
+            with_result(
+                row(alpha, "SIH", "Alpha", "verified", Some(("Ravi", "IT", "3"))),
+                "winner",
+                1,
+            ),
+            with_result(
+                row(beta, "SIH", "Beta", "verified", Some(("Zoe", "CSE", "2"))),
+                "runner_up",
+                2,
+            ),
+            row(
+                gamma,
+                "Codeathon",
+                "Gamma",
+                "pending",
+                Some(("Anu", "IT", "3")),
+            ),
+            with_result(
+                row(
+                    Uuid::new_v4(),
+                    "SIH",
+                    "Gone",
+                    "withdrawn",
+                    Some(("Kiran", "CSE", "2")),
+                ),
+                "winner",
+                1,
+            ),
+        ];
+
+        let summary = summary_rows(&group_teams(&rows));
+
+        assert_eq!(count(&summary, "Hackathons"), ["2"]);
+        assert_eq!(count(&summary, "Teams"), ["3"]);
+        assert_eq!(count(&summary, "Participations"), ["4"]);
+        assert_eq!(count(&summary, "Unique students"), ["3"]);
+        assert_eq!(count(&summary, "Faculty mentors"), ["1"]);
+        assert_eq!(count(&summary, "Teams with a result"), ["2"]);
+        assert_eq!(count(&summary, "Winning teams"), ["1"]);
+    }
+
+    #[test]
+    fn department_rows_ignore_case_and_surrounding_spaces() {
+        let (alpha, beta) = (Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![
+            row(
+                alpha,
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Anu", "cse ", "3")),
+            ),
+            row(
+                alpha,
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Ravi", "Cse", "3")),
+            ),
+            row(beta, "SIH", "Beta", "verified", Some(("Zoe", "CSE", "2"))),
+            row(beta, "SIH", "Beta", "verified", Some(("Kiran", "IT", "2"))),
+        ];
+
+        assert_eq!(
+            department_rows(&rows),
+            [["CSE", "3", "2"], ["IT", "1", "1"]]
+        );
+    }
+
+    #[test]
+    fn year_rows_follow_academic_year_order() {
+        let team = Uuid::new_v4();
+        let rows = vec![
+            row(
+                team,
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Anu", "IT", "Final")),
+            ),
+            row(
+                team,
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Ravi", "IT", "3rd Year")),
+            ),
+            row(
+                team,
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Zoe", "IT", "1st Year")),
+            ),
+            row(
+                team,
+                "SIH",
+                "Alpha",
+                "verified",
+                Some(("Kiran", "IT", "1st Year")),
+            ),
+        ];
+
+        assert_eq!(
+            year_rows(&rows),
+            [
+                ["1st Year", "2", "1"],
+                ["3rd Year", "1", "1"],
+                ["Final", "1", "1"]
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_keeps_short_text_on_one_line() {
+        assert_eq!(
+            wrap("Certificate of Merit", 200.0, 12.0),
+            ["Certificate of Merit"]
+        );
+    }
+
+    #[test]
+    fn wrap_breaks_between_words_within_the_width() {
+        // 10 chars at size 10 is about 17.6 mm
+        let lines = wrap("aaaa bbbb cccc dddd", 17.7, 10.0);
+        assert_eq!(lines, ["aaaa bbbb", "cccc dddd"]);
+        assert!(lines.iter().all(|line| text_width(line, 10.0) <= 17.7));
+    }
+
+    #[test]
+    fn wrap_puts_an_overlong_word_on_its_own_line() {
+        assert_eq!(
+            wrap("a supercalifragilistic b", 10.0, 10.0),
+            ["a", "supercalifragilistic", "b"]
+        );
+    }
+
+    #[test]
+    fn wrap_keeps_paragraph_breaks() {
+        assert_eq!(
+            wrap("first\n\nsecond", 200.0, 12.0),
+            ["first", "", "second"]
+        );
+    }
+
+    #[test]
+    fn wrap_collapses_repeated_spaces() {
+        assert_eq!(wrap("  spaced   out  ", 200.0, 12.0), ["spaced out"]);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::mailer::Email;
+use crate::models::{Hackathon, SubmissionStatus, SubmitParticipationRequest};
+use uuid::Uuid;
+
+// One confirmation email per participant, listing the team and a status link
+pub fn submission_receipts(
+    public_api_url: &str,
+    submission_id: Uuid,
+    hackathon: &Hackathon,
+    req: &SubmitParticipationRequest,
+    status: &SubmissionStatus,
+    waitlist_position: Option<i64>,
+) -> Vec<Email> {
+    let status_line = match waitlist_position {
+        Some(position) => format!("Waitlisted (position {})", position),
+        None => status.as_str().to_string(),
+    };
+
+    let participants = req
+        .participants
+        .iter()
+        .map(|p| {
+            format!(
+                "  - {} <{}>, {}, {}",
+                p.name, p.email, p.department, p.academic_year
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mentors = if req.mentors.is_empty() {
+        "  - None".to_string()
+    } else {
+        req.mentors
+            .iter()
+            .map(|m| format!("  - {}, {}", m.name, m.department))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    // Teams have no accounts, so the public status endpoint is the page they can open
+    let status_url = format!(
+        "{}/api/submissions/{}/status",
+        public_api_url.trim_end_matches('/'),
+        submission_id
+    );
+
+    req.participants
+        .iter()
+        .map(|participant| Email {
+            to: participant.email.clone(),
+            subject: format!("Registration received: {}", hackathon.name),
+            body: format!(
+                "Hi {},\n\n\
+                 Your team's participation in {} has been recorded.\n\n\
+                 Submission ID: {}\n\
+                 Team: {}\n\
+                 Status: {}\n\n\
+                 Participants:\n{}\n\n\
+                 Mentors:\n{}\n\n\
+                 View the status of your submission at {}\n\n\
+                 COIN SREC\n",
+                participant.name,
+                hackathon.name,
+                submission_id,
+                req.team_name,
+                status_line,
+                participants,
+                mentors,
+                status_url
+            ),
+        })
+        .collect()
+}
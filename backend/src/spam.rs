@@ -0,0 +1,273 @@
+use crate::error::{AppError, AppResult};
+use crate::AppState;
+use async_trait::async_trait;
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Fixed-window request counter per client IP. State is in memory, so limits
+// are per process and reset on restart.
+pub struct RateLimiter {
+    max_requests: u32,
+    window: Duration,
+    hits: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        Self {
+            max_requests,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let max_requests = std::env::var("RATE_LIMIT_REQUESTS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()?;
+        let window_secs: u64 = std::env::var("RATE_LIMIT_WINDOW_SECS")
+            .unwrap_or_else(|_| "600".to_string())
+            .parse()?;
+
+        Ok(Self::new(max_requests, Duration::from_secs(window_secs)))
+    }
+
+    // Records a request and returns false once the IP is over its limit
+    pub fn check(&self, ip: IpAddr) -> bool {
+        self.check_at(ip, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, now: Instant) -> bool {
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Drop expired windows so the map doesn't grow without bound
+        if hits.len() > 10_000 {
+            hits.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+        }
+
+        let entry = hits.entry(ip).or_insert((now, 0));
+        if now.duration_since(entry.0) >= self.window {
+            *entry = (now, 0);
+        }
+
+        entry.1 += 1;
+        entry.1 <= self.max_requests
+    }
+}
+
+// Address of the caller. X-Forwarded-For is only honoured when
+// TRUST_PROXY_HEADERS is set, since clients can send it themselves.
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for ClientIp {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> AppResult<Self> {
+        if state.trust_proxy_headers {
+            let forwarded = parts
+                .headers
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .and_then(|ip| ip.trim().parse().ok());
+
+            if forwarded.is_some() {
+                return Ok(ClientIp(forwarded));
+            }
+        }
+
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+
+        Ok(ClientIp(peer))
+    }
+}
+
+// Verifies the token produced by a CAPTCHA-style challenge on the frontend
+#[async_trait]
+pub trait ChallengeVerifier: Send + Sync {
+    async fn verify(&self, token: Option<&str>, ip: Option<IpAddr>) -> AppResult<bool>;
+}
+
+// Local stand-ins for development and tests
+pub struct AlwaysPass;
+
+#[async_trait]
+impl ChallengeVerifier for AlwaysPass {
+    async fn verify(&self, _token: Option<&str>, _ip: Option<IpAddr>) -> AppResult<bool> {
+        Ok(true)
+    }
+}
+
+pub struct AlwaysFail;
+
+#[async_trait]
+impl ChallengeVerifier for AlwaysFail {
+    async fn verify(&self, _token: Option<&str>, _ip: Option<IpAddr>) -> AppResult<bool> {
+        Ok(false)
+    }
+}
+
+// Talks to a siteverify-style endpoint (Turnstile, hCaptcha, reCAPTCHA), which
+// takes `secret`, `response` and `remoteip` form fields and answers `{"success": bool}`
+pub struct HttpVerifier {
+    client: reqwest::Client,
+    url: String,
+    secret: String,
+}
+
+#[derive(Deserialize)]
+struct VerifyResponse {
+    success: bool,
+}
+
+impl HttpVerifier {
+    pub fn new(url: String, secret: String) -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()?,
+            url,
+            secret,
+        })
+    }
+}
+
+#[async_trait]
+impl ChallengeVerifier for HttpVerifier {
+    async fn verify(&self, token: Option<&str>, ip: Option<IpAddr>) -> AppResult<bool> {
+        let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+            return Ok(false);
+        };
+
+        let mut form = vec![
+            ("secret", self.secret.clone()),
+            ("response", token.to_string()),
+        ];
+        if let Some(ip) = ip {
+            form.push(("remoteip", ip.to_string()));
+        }
+
+        let response = self
+            .client
+            .post(&self.url)
+            .form(&form)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| {
+                AppError::InternalError(format!("Challenge verification failed: {}", e))
+            })?;
+
+        let result: VerifyResponse = response.json().await.map_err(|e| {
+            AppError::InternalError(format!("Challenge verification failed: {}", e))
+        })?;
+
+        Ok(result.success)
+    }
+}
+
+pub fn verifier_from_env() -> Result<Arc<dyn ChallengeVerifier>, Box<dyn std::error::Error>> {
+    let backend = std::env::var("CHALLENGE_VERIFIER").unwrap_or_else(|_| "none".to_string());
+
+    match backend.as_str() {
+        "none" | "always_pass" => Ok(Arc::new(AlwaysPass)),
+        "always_fail" => Ok(Arc::new(AlwaysFail)),
+        "http" => {
+            let url = std::env::var("CHALLENGE_VERIFY_URL")?;
+            let secret = std::env::var("CHALLENGE_SECRET")?;
+            Ok(Arc::new(HttpVerifier::new(url, secret)?))
+        }
+        other => Err(format!("Unsupported CHALLENGE_VERIFIER: {}", other).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::from([10, 0, 0, last])
+    }
+
+    #[test]
+    fn allows_requests_up_to_the_limit() {
+        let limiter = RateLimiter::new(3, WINDOW);
+        let now = Instant::now();
+
+        assert!(limiter.check_at(ip(1), now));
+        assert!(limiter.check_at(ip(1), now));
+        assert!(limiter.check_at(ip(1), now));
+        assert!(!limiter.check_at(ip(1), now));
+        assert!(!limiter.check_at(ip(1), now + Duration::from_secs(59)));
+    }
+
+    #[test]
+    fn budget_is_per_ip() {
+        let limiter = RateLimiter::new(1, WINDOW);
+        let now = Instant::now();
+
+        assert!(limiter.check_at(ip(1), now));
+        assert!(!limiter.check_at(ip(1), now));
+        assert!(limiter.check_at(ip(2), now));
+        assert!(!limiter.check_at(ip(2), now));
+    }
+
+    #[test]
+    fn window_expiry_resets_the_count() {
+        let limiter = RateLimiter::new(2, WINDOW);
+        let start = Instant::now();
+
+        assert!(limiter.check_at(ip(1), start));
+        assert!(limiter.check_at(ip(1), start));
+        assert!(!limiter.check_at(ip(1), start));
+
+        let later = start + WINDOW;
+        assert!(limiter.check_at(ip(1), later));
+        assert!(limiter.check_at(ip(1), later));
+        assert!(!limiter.check_at(ip(1), later));
+    }
+
+    #[test]
+    fn expired_windows_are_pruned_from_large_maps() {
+        let limiter = RateLimiter::new(1, WINDOW);
+        let start = Instant::now();
+
+        for i in 0..=10_000u32 {
+            limiter.check_at(IpAddr::from(i.to_be_bytes()), start);
+        }
+        limiter.check_at(ip(1), start + WINDOW);
+
+        assert_eq!(limiter.hits.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stand_in_verifiers() {
+        assert!(AlwaysPass.verify(None, None).await.unwrap());
+        assert!(!AlwaysFail.verify(Some("token"), Some(ip(1))).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn http_verifier_rejects_missing_token_without_calling_out() {
+        let verifier = HttpVerifier::new(
+            "http://127.0.0.1:9/verify".to_string(),
+            "secret".to_string(),
+        )
+        .unwrap();
+
+        assert!(!verifier.verify(None, None).await.unwrap());
+        assert!(!verifier.verify(Some("   "), None).await.unwrap());
+    }
+}
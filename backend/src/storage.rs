@@ -0,0 +1,140 @@
+use crate::error::{AppError, AppResult};
+use async_trait::async_trait;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+// Blob storage for uploaded files. Keys are relative, slash-separated paths
+// such as `submissions/<submission_id>/<document_id>.pdf`.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn put(&self, key: &str, data: &[u8]) -> AppResult<()>;
+    async fn get(&self, key: &str) -> AppResult<Vec<u8>>;
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
+
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // Rejects keys that could escape the storage root
+    fn path_for(&self, key: &str) -> AppResult<PathBuf> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+
+        if !is_safe {
+            return Err(AppError::InternalError(format!(
+                "Invalid storage key: {}",
+                key
+            )));
+        }
+
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, data: &[u8]) -> AppResult<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| AppError::InternalError(format!("Storage error: {}", e)))?;
+        }
+
+        tokio::fs::write(&path, data)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Storage error: {}", e)))
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Vec<u8>> {
+        let path = self.path_for(key)?;
+        tokio::fs::read(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AppError::NotFound("Stored file not found".to_string())
+            } else {
+                AppError::InternalError(format!("Storage error: {}", e))
+            }
+        })
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::InternalError(format!("Storage error: {}", e))),
+        }
+    }
+}
+
+pub fn from_env() -> Result<Arc<dyn Storage>, Box<dyn std::error::Error>> {
+    let backend = std::env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string());
+
+    match backend.as_str() {
+        "local" => {
+            let root =
+                std::env::var("STORAGE_LOCAL_PATH").unwrap_or_else(|_| "./uploads".to_string());
+            Ok(Arc::new(LocalStorage::new(root)))
+        }
+        other => Err(format!("Unsupported STORAGE_BACKEND: {}", other).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage() -> LocalStorage {
+        LocalStorage::new("/srv/uploads")
+    }
+
+    #[test]
+    fn path_for_joins_relative_keys_under_the_root() {
+        assert_eq!(
+            storage().path_for("submissions/abc/doc.pdf").unwrap(),
+            PathBuf::from("/srv/uploads/submissions/abc/doc.pdf")
+        );
+    }
+
+    #[test]
+    fn path_for_rejects_traversal_and_absolute_keys() {
+        for key in [
+            "",
+            "..",
+            "../secret",
+            "submissions/../../etc/passwd",
+            "/etc/passwd",
+            "./doc.pdf",
+        ] {
+            assert!(storage().path_for(key).is_err(), "accepted {:?}", key);
+        }
+    }
+
+    #[tokio::test]
+    async fn put_get_delete_round_trip() {
+        let root = std::env::temp_dir().join(format!("coin-storage-{}", uuid::Uuid::new_v4()));
+        let storage = LocalStorage::new(&root);
+
+        storage.put("a/b/doc.pdf", b"%PDF-1.7").await.unwrap();
+        assert_eq!(storage.get("a/b/doc.pdf").await.unwrap(), b"%PDF-1.7");
+
+        storage.delete("a/b/doc.pdf").await.unwrap();
+        assert!(matches!(
+            storage.get("a/b/doc.pdf").await,
+            Err(AppError::NotFound(_))
+        ));
+        // Deleting twice is not an error
+        storage.delete("a/b/doc.pdf").await.unwrap();
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
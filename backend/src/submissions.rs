@@ -0,0 +1,257 @@
+use crate::certificates;
+use crate::error::AppResult;
+use crate::events;
+use crate::models::{Submission, SubmissionStatus};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+// Statuses that occupy one of a hackathon's team slots
+const SEAT_HOLDING_STATUSES: [&str; 2] = ["submitted", "verified"];
+
+fn holds_seat(status: &str) -> bool {
+    SEAT_HOLDING_STATUSES.contains(&status)
+}
+
+// Locks the hackathon row so capacity checks and waitlist promotions for the
+// same hackathon run one at a time. Returns the team capacity, if any.
+async fn lock_capacity(conn: &mut PgConnection, hackathon_id: Uuid) -> AppResult<Option<i32>> {
+    let capacity: Option<Option<i32>> =
+        sqlx::query_scalar("SELECT team_capacity FROM hackathons WHERE id = $1 FOR UPDATE")
+            .bind(hackathon_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+    Ok(capacity.flatten())
+}
+
+async fn seats_taken(conn: &mut PgConnection, hackathon_id: Uuid) -> AppResult<i64> {
+    let taken = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM submissions WHERE hackathon_id = $1 AND status = ANY($2)",
+    )
+    .bind(hackathon_id)
+    .bind(&SEAT_HOLDING_STATUSES[..])
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(taken)
+}
+
+// Status a new submission should start in. Keeps the hackathon row locked
+// until the caller's transaction ends, so concurrent submissions can't both
+// take the last seat.
+pub async fn initial_status(
+    conn: &mut PgConnection,
+    hackathon_id: Uuid,
+) -> AppResult<SubmissionStatus> {
+    let Some(capacity) = lock_capacity(conn, hackathon_id).await? else {
+        return Ok(SubmissionStatus::Submitted);
+    };
+
+    if seats_taken(conn, hackathon_id).await? < capacity as i64 {
+        Ok(SubmissionStatus::Submitted)
+    } else {
+        Ok(SubmissionStatus::Waitlisted)
+    }
+}
+
+// Locks a submission and its hackathon, hackathon first. Every seat change
+// takes the locks in this order, as `initial_status` does for new submissions,
+// so concurrent status changes and submissions can't deadlock. Returns the
+// hackathon ID and current status.
+pub async fn lock_submission(
+    conn: &mut PgConnection,
+    submission_id: Uuid,
+) -> AppResult<Option<(Uuid, String)>> {
+    let hackathon_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT hackathon_id FROM submissions WHERE id = $1")
+            .bind(submission_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+    let Some(hackathon_id) = hackathon_id else {
+        return Ok(None);
+    };
+    lock_capacity(conn, hackathon_id).await?;
+
+    let status: Option<String> =
+        sqlx::query_scalar("SELECT status FROM submissions WHERE id = $1 FOR UPDATE")
+            .bind(submission_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+    Ok(status.map(|status| (hackathon_id, status)))
+}
+
+// Bulk version of `lock_submission`. Hackathons and submissions are each
+// locked in ID order so two bulk operations can't deadlock each other either.
+pub async fn lock_submissions(conn: &mut PgConnection, submission_ids: &[Uuid]) -> AppResult<()> {
+    sqlx::query(
+        "SELECT id FROM hackathons
+         WHERE id IN (SELECT hackathon_id FROM submissions WHERE id = ANY($1))
+         ORDER BY id
+         FOR UPDATE",
+    )
+    .bind(submission_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("SELECT id FROM submissions WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+        .bind(submission_ids)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+// Moves waitlisted teams into free seats, oldest first. `exclude` keeps a
+// team that was just moved to the waitlist from taking its own seat back.
+pub async fn promote_from_waitlist(
+    conn: &mut PgConnection,
+    hackathon_id: Uuid,
+    exclude: Option<Uuid>,
+) -> AppResult<Vec<Submission>> {
+    let free_seats = match lock_capacity(conn, hackathon_id).await? {
+        Some(capacity) => Some(capacity as i64 - seats_taken(conn, hackathon_id).await?),
+        None => None, // no capacity limit, so everyone on the waitlist gets in
+    };
+
+    if matches!(free_seats, Some(free) if free <= 0) {
+        return Ok(Vec::new());
+    }
+
+    let promoted: Vec<Submission> = sqlx::query_as(
+        "UPDATE submissions SET status = 'submitted'
+         WHERE id IN (
+             SELECT id FROM submissions
+             WHERE hackathon_id = $1 AND status = 'waitlisted'
+               AND ($3::uuid IS NULL OR id <> $3)
+             ORDER BY waitlisted_at, created_at, id
+             LIMIT $2
+         )
+         RETURNING *",
+    )
+    .bind(hackathon_id)
+    .bind(free_seats)
+    .bind(exclude)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for submission in &promoted {
+        tracing::info!(
+            "Promoted submission {} from the waitlist of hackathon {}",
+            submission.id,
+            hackathon_id
+        );
+        events::submission_status_changed(conn, submission, "waitlisted").await?;
+    }
+
+    Ok(promoted)
+}
+
+// Finds an active submission from the same team (same name and the same set of
+// participant emails) so a resubmitted form doesn't register the team twice
+pub async fn find_identical(
+    conn: &mut PgConnection,
+    hackathon_id: Uuid,
+    team_name: &str,
+    emails: &[String],
+) -> AppResult<Option<(Uuid, String)>> {
+    let mut emails: Vec<String> = emails.iter().map(|e| e.trim().to_lowercase()).collect();
+    emails.sort();
+
+    let existing = sqlx::query_as(
+        "SELECT s.id, s.status
+         FROM submissions s
+         WHERE s.hackathon_id = $1
+           AND LOWER(TRIM(s.team_name)) = LOWER(TRIM($2))
+           AND s.status NOT IN ('withdrawn', 'rejected')
+           AND (SELECT ARRAY_AGG(LOWER(TRIM(p.email)) ORDER BY LOWER(TRIM(p.email)))
+                FROM participants p WHERE p.submission_id = s.id) = $3
+         ORDER BY s.created_at
+         LIMIT 1",
+    )
+    .bind(hackathon_id)
+    .bind(team_name)
+    .bind(emails)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(existing)
+}
+
+// 1-based position of a waitlisted submission; `None` when it isn't waitlisted
+pub async fn waitlist_position(
+    conn: &mut PgConnection,
+    submission_id: Uuid,
+) -> AppResult<Option<i64>> {
+    let position: Option<i64> = sqlx::query_scalar(
+        "SELECT COUNT(*)
+         FROM submissions me
+         JOIN submissions w ON w.hackathon_id = me.hackathon_id AND w.status = 'waitlisted'
+         WHERE me.id = $1
+           AND me.status = 'waitlisted'
+           AND (w.waitlisted_at, w.created_at, w.id) <= (me.waitlisted_at, me.created_at, me.id)
+         GROUP BY me.id",
+    )
+    .bind(submission_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(position)
+}
+
+// Single place where a submission's status changes. When a team gives up its
+// seat (withdrawn, rejected, archived) the next waitlisted team is promoted.
+pub async fn set_status(
+    conn: &mut PgConnection,
+    submission_id: Uuid,
+    status: &SubmissionStatus,
+) -> AppResult<Option<Submission>> {
+    let Some((_, previous)) = lock_submission(conn, submission_id).await? else {
+        return Ok(None);
+    };
+
+    let submission: Submission = sqlx::query_as(
+        "UPDATE submissions SET
+            status = $2,
+            waitlisted_at = CASE
+                WHEN $2 = 'waitlisted' AND status <> 'waitlisted' THEN NOW()
+                ELSE waitlisted_at
+            END
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(submission_id)
+    .bind(status.as_str())
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if previous != submission.status {
+        events::submission_status_changed(conn, &submission, &previous).await?;
+        certificates::sync_revocation(conn, submission.id, &submission.status).await?;
+    }
+
+    if holds_seat(&previous) && !holds_seat(&submission.status) {
+        promote_from_waitlist(conn, submission.hackathon_id, Some(submission.id)).await?;
+    }
+
+    Ok(Some(submission))
+}
+
+// Deletes a submission; a freed seat goes to the next waitlisted team
+pub async fn delete(conn: &mut PgConnection, submission_id: Uuid) -> AppResult<bool> {
+    let Some((hackathon_id, status)) = lock_submission(conn, submission_id).await? else {
+        return Ok(false);
+    };
+
+    sqlx::query("DELETE FROM submissions WHERE id = $1")
+        .bind(submission_id)
+        .execute(&mut *conn)
+        .await?;
+
+    if holds_seat(&status) {
+        promote_from_waitlist(conn, hackathon_id, None).await?;
+    }
+
+    Ok(true)
+}
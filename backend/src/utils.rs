@@ -16,6 +16,19 @@ pub fn validate_srec_email(email: &str) -> AppResult<()> {
     }
 }
 
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+pub fn validate_password(password: &str) -> AppResult<()> {
+    if password.chars().count() >= MIN_PASSWORD_LENGTH {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )))
+    }
+}
+
 pub fn generate_slug(title: &str) -> String {
     title
         .to_lowercase()
@@ -32,3 +45,143 @@ pub fn generate_slug(title: &str) -> String {
 pub fn validate_uuid(id: &str) -> AppResult<uuid::Uuid> {
     uuid::Uuid::parse_str(id).map_err(|_| AppError::BadRequest("Invalid UUID format".to_string()))
 }
+
+pub const ALLOWED_DOCUMENT_TYPES: [&str; 3] = ["application/pdf", "image/png", "image/jpeg"];
+
+// Detects the MIME type of an uploaded document from its magic bytes
+pub fn detect_document_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else {
+        None
+    }
+}
+
+pub fn sanitize_file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(200)
+        .collect();
+
+    if cleaned.trim_matches('.').is_empty() {
+        "document".to_string()
+    } else {
+        cleaned
+    }
+}
+
+// Maps free-form academic years ("First Year", "2nd Year", "3") to 1-4
+pub fn academic_year_number(year: &str) -> Option<i32> {
+    let normalized = year.trim().to_lowercase();
+    let first_word = normalized.split_whitespace().next()?;
+
+    match first_word {
+        "1" | "1st" | "first" | "i" => Some(1),
+        "2" | "2nd" | "second" | "ii" => Some(2),
+        "3" | "3rd" | "third" | "iii" => Some(3),
+        "4" | "4th" | "fourth" | "iv" => Some(4),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_supported_documents_by_magic_bytes() {
+        assert_eq!(
+            detect_document_type(b"%PDF-1.7\n..."),
+            Some("application/pdf")
+        );
+        assert_eq!(
+            detect_document_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some("image/png")
+        );
+        assert_eq!(
+            detect_document_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00]),
+            Some("image/jpeg")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_truncated_documents() {
+        assert_eq!(detect_document_type(b""), None);
+        assert_eq!(detect_document_type(b"%PD"), None);
+        assert_eq!(detect_document_type(b"PK\x03\x04"), None);
+        assert_eq!(detect_document_type(b"<html>%PDF-"), None);
+        assert_eq!(detect_document_type(&[0xFF, 0xD8]), None);
+    }
+
+    #[test]
+    fn sanitize_keeps_safe_names() {
+        assert_eq!(sanitize_file_name("proof-2026_v2.pdf"), "proof-2026_v2.pdf");
+        assert_eq!(sanitize_file_name("résumé.pdf"), "résumé.pdf");
+    }
+
+    #[test]
+    fn sanitize_replaces_unsafe_characters() {
+        assert_eq!(
+            sanitize_file_name("my report (final).pdf"),
+            "my_report__final_.pdf"
+        );
+        assert_eq!(sanitize_file_name("a\0b\"c.pdf"), "a_b_c.pdf");
+    }
+
+    #[test]
+    fn sanitize_strips_directories() {
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("..\\..\\boot.ini"), "boot.ini");
+        assert_eq!(sanitize_file_name("/tmp/upload.png"), "upload.png");
+    }
+
+    #[test]
+    fn sanitize_falls_back_for_empty_or_dot_names() {
+        assert_eq!(sanitize_file_name(""), "document");
+        assert_eq!(sanitize_file_name(".."), "document");
+        assert_eq!(sanitize_file_name("uploads/"), "document");
+    }
+
+    #[test]
+    fn sanitize_truncates_long_names() {
+        let name = format!("{}.pdf", "a".repeat(300));
+        assert_eq!(sanitize_file_name(&name).chars().count(), 200);
+    }
+
+    #[test]
+    fn parses_academic_year_spellings() {
+        assert_eq!(academic_year_number("1"), Some(1));
+        assert_eq!(academic_year_number("First Year"), Some(1));
+        assert_eq!(academic_year_number(" 2nd year "), Some(2));
+        assert_eq!(academic_year_number("III"), Some(3));
+        assert_eq!(academic_year_number("fourth"), Some(4));
+    }
+
+    #[test]
+    fn rejects_unknown_academic_years() {
+        assert_eq!(academic_year_number(""), None);
+        assert_eq!(academic_year_number("   "), None);
+        assert_eq!(academic_year_number("Final Year"), None);
+        assert_eq!(academic_year_number("5th Year"), None);
+        assert_eq!(academic_year_number("Year 2"), None);
+    }
+
+    #[test]
+    fn password_needs_the_minimum_length() {
+        assert!(validate_password("").is_err());
+        assert!(validate_password("short12").is_err());
+        assert!(validate_password("longer12").is_ok());
+    }
+}
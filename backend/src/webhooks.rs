@@ -0,0 +1,319 @@
+use crate::error::{AppError, AppResult};
+use crate::jobs;
+use crate::models::{Webhook, WebhookDelivery};
+use crate::AppState;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use sqlx::types::JsonValue;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+const MAX_LOGGED_RESPONSE_CHARS: usize = 2000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliveryJob {
+    pub delivery_id: Uuid,
+}
+
+// Records a delivery for every active webhook subscribed to `event` and queues
+// it. Runs on the caller's connection so nothing is sent for rolled-back changes.
+pub async fn dispatch(conn: &mut PgConnection, event: &str, data: JsonValue) -> AppResult<()> {
+    let webhook_ids: Vec<Uuid> =
+        sqlx::query_scalar("SELECT id FROM webhooks WHERE active AND $1 = ANY(events)")
+            .bind(event)
+            .fetch_all(&mut *conn)
+            .await?;
+
+    for webhook_id in webhook_ids {
+        let delivery_id = Uuid::new_v4();
+        let payload = json!({
+            "id": delivery_id,
+            "event": event,
+            "created_at": Utc::now(),
+            "data": data,
+        });
+
+        sqlx::query(
+            "INSERT INTO webhook_deliveries (id, webhook_id, event, payload) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(delivery_id)
+        .bind(webhook_id)
+        .bind(event)
+        .bind(payload)
+        .execute(&mut *conn)
+        .await?;
+
+        jobs::enqueue(conn, jobs::DELIVER_WEBHOOK, &DeliveryJob { delivery_id }).await?;
+    }
+
+    Ok(())
+}
+
+// `sha256=<hex>` HMAC of `<timestamp>.<body>`, so receivers can reject replays
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+// Outcome of one HTTP attempt, as recorded in the delivery log
+struct Attempt {
+    response_status: Option<i32>,
+    response_body: Option<String>,
+    error: Option<String>,
+}
+
+// Posts the signed payload. Any non-2xx answer counts as a failure.
+async fn send(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    delivery: &WebhookDelivery,
+) -> AppResult<Attempt> {
+    let body = serde_json::to_vec(&delivery.payload)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode webhook payload: {}", e)))?;
+    let timestamp = Utc::now().timestamp();
+
+    let result = client
+        .post(&webhook.url)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "COIN-Webhooks/1.0")
+        .header("X-Coin-Event", &delivery.event)
+        .header("X-Coin-Delivery", delivery.id.to_string())
+        .header("X-Coin-Timestamp", timestamp.to_string())
+        .header("X-Coin-Signature", sign(&webhook.secret, timestamp, &body))
+        .body(body)
+        .send()
+        .await;
+
+    Ok(match result {
+        Ok(response) => {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            let text: String = text.chars().take(MAX_LOGGED_RESPONSE_CHARS).collect();
+            Attempt {
+                response_status: Some(status.as_u16() as i32),
+                response_body: Some(text),
+                error: (!status.is_success()).then(|| format!("Endpoint returned {}", status)),
+            }
+        }
+        Err(e) => Attempt {
+            response_status: None,
+            response_body: None,
+            error: Some(e.to_string()),
+        },
+    })
+}
+
+// A failed attempt stays `retrying` while the job queue has attempts left
+fn delivery_status(attempt: &Attempt, final_attempt: bool) -> &'static str {
+    match (&attempt.error, final_attempt) {
+        (None, _) => "delivered",
+        (Some(_), false) => "retrying",
+        (Some(_), true) => "failed",
+    }
+}
+
+// Sends one delivery. An error makes the job queue retry it with backoff;
+// `final_attempt` is set when the queue will not retry again.
+pub async fn deliver(state: &AppState, delivery_id: Uuid, final_attempt: bool) -> AppResult<()> {
+    let delivery: Option<WebhookDelivery> =
+        sqlx::query_as("SELECT * FROM webhook_deliveries WHERE id = $1")
+            .bind(delivery_id)
+            .fetch_optional(&state.db)
+            .await?;
+
+    // Deliveries are removed along with their webhook
+    let Some(delivery) = delivery else {
+        return Ok(());
+    };
+
+    if delivery.status == "delivered" {
+        return Ok(());
+    }
+
+    let webhook: Webhook = sqlx::query_as("SELECT * FROM webhooks WHERE id = $1")
+        .bind(delivery.webhook_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let attempt = send(&state.http_client, &webhook, &delivery).await?;
+
+    sqlx::query(
+        "UPDATE webhook_deliveries SET
+            status = $2,
+            attempts = attempts + 1,
+            response_status = $3,
+            response_body = $4,
+            error = $5,
+            last_attempt_at = NOW(),
+            delivered_at = CASE WHEN $2 = 'delivered' THEN NOW() END
+         WHERE id = $1",
+    )
+    .bind(delivery_id)
+    .bind(delivery_status(&attempt, final_attempt))
+    .bind(attempt.response_status)
+    .bind(&attempt.response_body)
+    .bind(&attempt.error)
+    .execute(&state.db)
+    .await?;
+
+    match attempt.error {
+        None => Ok(()),
+        Some(error) => Err(AppError::InternalError(format!(
+            "Webhook delivery to {} failed: {}",
+            webhook.url, error
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, extract::State, http::HeaderMap, http::StatusCode, routing::post};
+    use std::sync::{Arc, Mutex};
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    // Local stand-in receiver: answers the first `failures` requests with a 500
+    async fn start_receiver(failures: usize) -> (String, Received) {
+        let received: Received = Arc::default();
+        let app = axum::Router::new()
+            .route(
+                "/hook",
+                post(
+                    move |State(received): State<Received>, headers: HeaderMap, body: Bytes| async move {
+                        let mut received = received.lock().unwrap();
+                        received.push((headers, body));
+                        if received.len() <= failures {
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        } else {
+                            StatusCode::OK
+                        }
+                    },
+                ),
+            )
+            .with_state(received.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (url, received)
+    }
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            id: Uuid::new_v4(),
+            url: url.to_string(),
+            secret: "whsec_test".to_string(),
+            events: vec!["submission.created".to_string()],
+            description: None,
+            active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn delivery(webhook: &Webhook) -> WebhookDelivery {
+        WebhookDelivery {
+            id: Uuid::new_v4(),
+            webhook_id: webhook.id,
+            event: "submission.created".to_string(),
+            payload: json!({ "event": "submission.created", "data": { "team_name": "Robo" } }),
+            status: "pending".to_string(),
+            attempts: 0,
+            response_status: None,
+            response_body: None,
+            error: None,
+            created_at: Utc::now(),
+            last_attempt_at: None,
+            delivered_at: None,
+        }
+    }
+
+    #[test]
+    fn sign_matches_known_vector() {
+        // printf '1700000000.{"hello":"world"}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", 1_700_000_000, br#"{"hello":"world"}"#),
+            "sha256=654f06c856baf080af3fa272934823257a542d35cf1f88099338f850a60601a4"
+        );
+    }
+
+    #[tokio::test]
+    async fn delivery_is_signed() {
+        let (url, received) = start_receiver(0).await;
+        let webhook = webhook(&url);
+        let delivery = delivery(&webhook);
+
+        let attempt = send(&reqwest::Client::new(), &webhook, &delivery)
+            .await
+            .unwrap();
+        assert_eq!(attempt.response_status, Some(200));
+        assert_eq!(delivery_status(&attempt, false), "delivered");
+
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[0];
+        let header = |name: &str| headers.get(name).unwrap().to_str().unwrap().to_string();
+        let timestamp: i64 = header("x-coin-timestamp").parse().unwrap();
+
+        assert_eq!(
+            header("x-coin-signature"),
+            sign("whsec_test", timestamp, body)
+        );
+        assert_eq!(header("x-coin-event"), "submission.created");
+        assert_eq!(header("x-coin-delivery"), delivery.id.to_string());
+        assert_eq!(
+            serde_json::from_slice::<JsonValue>(body).unwrap(),
+            delivery.payload
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_retry_until_the_last_one() {
+        let (url, received) = start_receiver(2).await;
+        let webhook = webhook(&url);
+        let delivery = delivery(&webhook);
+        let client = reqwest::Client::new();
+
+        let first = send(&client, &webhook, &delivery).await.unwrap();
+        assert_eq!(first.response_status, Some(500));
+        assert_eq!(delivery_status(&first, false), "retrying");
+
+        let second = send(&client, &webhook, &delivery).await.unwrap();
+        assert_eq!(delivery_status(&second, true), "failed");
+
+        let third = send(&client, &webhook, &delivery).await.unwrap();
+        assert_eq!(delivery_status(&third, false), "delivered");
+        assert_eq!(received.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoint_is_a_failed_attempt() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        let webhook = webhook(&url);
+        let attempt = send(&reqwest::Client::new(), &webhook, &delivery(&webhook))
+            .await
+            .unwrap();
+
+        assert_eq!(attempt.response_status, None);
+        assert!(attempt.error.is_some());
+        assert_eq!(delivery_status(&attempt, false), "retrying");
+    }
+}
@@ -19,7 +19,7 @@ async function fetcher<T>(endpoint: string, options: RequestOptions = {}): Promi
     const token = typeof window !== 'undefined' ? localStorage.getItem('coin_token') : null
 
     const headers = new Headers(init.headers)
-    if (token) {
+    if (token && !headers.has('Authorization')) {
         headers.set('Authorization', `Bearer ${token}`)
     }
     if (!headers.has('Content-Type') && !(init.body instanceof FormData)) {
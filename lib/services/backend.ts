@@ -4,7 +4,7 @@
  */
 
 import { api } from '../api'
-import { Hackathon, Submission, BlogPost, DashboardMetrics, Participant, Mentor, BlogStatus } from '../types'
+import { Hackathon, Submission, BlogPost, DashboardMetrics, Participant, Mentor, BlogStatus, FacultyMember, Mentorship } from '../types'
 
 // Types for backend responses
 interface BackendHackathon {
@@ -65,6 +65,9 @@ interface BackendMentor {
   submission_id: string
   name: string
   department: string
+  faculty_id?: string | null
+  status: string
+  response_note?: string | null
 }
 
 interface BackendSubmissionDetail {
@@ -95,6 +98,30 @@ interface StudentLoginResponse {
   }
 }
 
+interface FacultyLoginResponse {
+  token: string
+  faculty: {
+    id: string
+    name: string
+    email: string
+    department: string
+    designation?: string
+  }
+}
+
+interface BackendMentorship {
+  mentor_id: string
+  status: string
+  response_note?: string | null
+  responded_at?: string | null
+  submission_id: string
+  team_name: string
+  requested_at: string
+  hackathon_name: string
+  semester: string
+  participants: BackendParticipant[]
+}
+
 interface MetricsResponse {
   total_hackathons: number
   total_submissions: number
@@ -339,7 +366,9 @@ export const backendService = {
         })),
         mentors: data.mentors.map((m) => ({
           name: m.name,
-          department: m.department as any
+          department: m.department as any,
+          facultyId: m.faculty_id || undefined,
+          status: m.status as any
         })),
         externalConfirmed: data.submission.external_registration_confirmed,
         externalRegistrationConfirmed: data.submission.external_registration_confirmed,
@@ -363,10 +392,31 @@ export const backendService = {
         department: p.department,
         academic_year: p.academicYear,
       })),
-      mentors: submission.mentors.map((m: any) => ({
-        name: m.name,
-        department: m.department,
-      })),
+      // Faculty picked from the directory are sent by ID and must accept the request
+      mentors: submission.mentors.map((m: Mentor) =>
+        m.facultyId
+          ? { faculty_id: m.facultyId }
+          : { name: m.name, department: m.department }
+      ),
+    })
+  },
+
+  async getFaculty(department?: string): Promise<FacultyMember[]> {
+    return api.get<FacultyMember[]>('/faculty', {
+      params: { department },
+    })
+  },
+
+  // Asks another faculty member in place of a mentor who declined
+  async replaceMentor(
+    submissionId: string,
+    mentorId: string,
+    email: string,
+    facultyId: string
+  ): Promise<void> {
+    await api.put(`/submissions/${submissionId}/mentors/${mentorId}`, {
+      email,
+      faculty_id: facultyId,
     })
   },
 
@@ -388,6 +438,50 @@ export const backendService = {
     })
   },
 
+  // Faculty endpoints take the faculty token explicitly, so an admin signed in
+  // on the same browser doesn't take their place
+  async facultyLogin(email: string, password: string): Promise<FacultyLoginResponse> {
+    return api.post<FacultyLoginResponse>('/faculty/login', {
+      email,
+      password,
+    })
+  },
+
+  async getMentorships(token: string): Promise<Mentorship[]> {
+    const data = await api.get<BackendMentorship[]>('/faculty/mentorships', {
+      headers: { Authorization: `Bearer ${token}` },
+    })
+    return data.map((m) => ({
+      mentorId: m.mentor_id,
+      status: m.status as any,
+      responseNote: m.response_note || undefined,
+      respondedAt: m.responded_at || undefined,
+      submissionId: m.submission_id,
+      teamName: m.team_name,
+      hackathonName: m.hackathon_name,
+      semester: m.semester,
+      requestedAt: m.requested_at,
+      participants: m.participants.map((p) => ({
+        name: p.name,
+        department: p.department,
+        academicYear: p.academic_year,
+      })),
+    }))
+  },
+
+  async respondToMentorship(
+    token: string,
+    mentorId: string,
+    status: 'approved' | 'declined',
+    note?: string
+  ): Promise<void> {
+    await api.put(
+      `/faculty/mentorships/${mentorId}`,
+      { status, note },
+      { headers: { Authorization: `Bearer ${token}` } }
+    )
+  },
+
   // Student endpoints
   async studentRegister(data: {
     name: string
@@ -441,6 +535,16 @@ export const backendService = {
     }
   },
 
+  // Live dashboard updates. EventSource can't send the Authorization header, so
+  // it connects with a one-minute ticket instead; when the connection drops,
+  // open a new stream rather than relying on EventSource's own reconnect.
+  async openAdminEvents(): Promise<EventSource> {
+    const { ticket } = await api.post<{ ticket: string }>('/admin/events/ticket')
+    return new EventSource(
+      `${process.env.NEXT_PUBLIC_API_URL || 'http://127.0.0.1:8000/api'}/admin/events?ticket=${encodeURIComponent(ticket)}`
+    )
+  },
+
   // Export
   async exportData(filters?: any): Promise<Blob> {
     const response = await fetch(
@@ -34,9 +34,35 @@ export interface Participant {
   academicYear: string
 }
 
+export type MentorStatus = 'pending' | 'approved' | 'declined'
+
 export interface Mentor {
   name: string
   department: string
+  // Set when the mentor was picked from the faculty directory
+  facultyId?: string
+  status?: MentorStatus
+}
+
+export interface FacultyMember {
+  id: string
+  name: string
+  department: string
+  designation?: string
+}
+
+// A team that asked a faculty member to mentor it
+export interface Mentorship {
+  mentorId: string
+  status: MentorStatus
+  responseNote?: string
+  respondedAt?: string
+  submissionId: string
+  teamName: string
+  hackathonName: string
+  semester: string
+  requestedAt: string
+  participants: { name: string; department: string; academicYear: string }[]
 }
 
 export type SubmissionStatus = 'submitted' | 'verified' | 'archived'